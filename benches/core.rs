@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate criterion;
+extern crate lcs;
+
+use criterion::{BenchmarkId, Criterion};
+use lcs::LcsTable;
+
+/// The sizes of the inputs, and how many elements in a thousand are left unchanged between them.
+const SIZES: &[usize] = &[100, 1_000, 5_000];
+const SIMILARITIES: &[u32] = &[500, 900, 990];
+
+/// A sequence of `len` pseudorandom bytes, and a copy where all but about `similarity` elements in
+/// a thousand are changed. Half of the changes are substitutions, and the rest are deletions.
+fn inputs(len: usize, similarity: u32) -> (Vec<u8>, Vec<u8>) {
+    let hash = |i: u32| i.wrapping_mul(2_654_435_761);
+
+    let a: Vec<u8> = (0..len as u32).map(|i| (hash(i) >> 24) as u8).collect();
+    let b = a.iter().enumerate()
+        .filter_map(|(i, &x)| match hash(i as u32 ^ 0x5bd1_e995) % 1_000 {
+            r if r < similarity => Some(x),
+            r if r % 2 == 0 => Some(x.wrapping_add(1)),
+            _ => None
+        })
+        .collect();
+
+    (a, b)
+}
+
+fn bench_core(c: &mut Criterion) {
+    let mut group = c.benchmark_group("core");
+    group.sample_size(10);
+
+    for &len in SIZES {
+        for &similarity in SIMILARITIES {
+            let (a, b) = inputs(len, similarity);
+            let table = LcsTable::new(&a, &b);
+            let parameter = format!("{}/{}", len, similarity);
+
+            group.bench_with_input(BenchmarkId::new("new", &parameter), &(&a, &b),
+                                   |bencher, &(a, b)| {
+                bencher.iter(|| LcsTable::new(a, b).length())
+            });
+            group.bench_with_input(BenchmarkId::new("diff", &parameter), &table, |bencher, table| {
+                bencher.iter(|| table.diff().len())
+            });
+            group.bench_with_input(BenchmarkId::new("longest_common_subsequence", &parameter),
+                                   &table, |bencher, table| {
+                bencher.iter(|| table.longest_common_subsequence().len())
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Enumerating every longest common subsequence can take time exponential in the input size, so
+/// this only uses the smallest inputs, where there are few enough to finish.
+fn bench_all_subsequences(c: &mut Criterion) {
+    let mut group = c.benchmark_group("longest_common_subsequences");
+    group.sample_size(10);
+
+    for &similarity in SIMILARITIES {
+        let (a, b) = inputs(SIZES[0], similarity);
+        let table = LcsTable::new(&a, &b);
+
+        group.bench_with_input(BenchmarkId::from_parameter(similarity), &table, |bencher, table| {
+            bencher.iter(|| table.longest_common_subsequences().len())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_core, bench_all_subsequences);
+criterion_main!(benches);
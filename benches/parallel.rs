@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate criterion;
+extern crate lcs;
+
+use criterion::Criterion;
+use lcs::LcsTable;
+
+/// Two 10,000-element sequences that are similar, but not so similar that the table is trivial.
+fn inputs() -> (Vec<u8>, Vec<u8>) {
+    let a: Vec<u8> = (0..10_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 28) as u8).collect();
+    let b: Vec<u8> = a.iter().enumerate()
+        .map(|(i, &x)| if i % 7 == 0 { x ^ 1 } else { x })
+        .collect();
+
+    (a, b)
+}
+
+fn bench_new(c: &mut Criterion) {
+    let (a, b) = inputs();
+
+    let mut group = c.benchmark_group("10k x 10k");
+    group.sample_size(10);
+    group.bench_function("new", |bencher| bencher.iter(|| LcsTable::new(&a, &b).length()));
+    group.bench_function("new_parallel", |bencher| {
+        bencher.iter(|| LcsTable::new_parallel(&a, &b).length())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_new);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+#[macro_use]
+extern crate criterion;
+extern crate lcs;
+
+use criterion::Criterion;
+use lcs::{LcsTable, LcsWorkspace};
+
+/// A thousand pairs of short sequences, like the lines of two revisions of a file.
+fn inputs() -> Vec<(Vec<u8>, Vec<u8>)> {
+    (0..1_000u32)
+        .map(|n| {
+            let a: Vec<u8> = (0..40u32)
+                .map(|i| ((n * 40 + i).wrapping_mul(2_654_435_761) >> 29) as u8)
+                .collect();
+            let b = a.iter().enumerate()
+                .map(|(i, &x)| if i % 5 == 0 { x ^ 1 } else { x })
+                .collect();
+
+            (a, b)
+        })
+        .collect()
+}
+
+fn bench_many_pairs(c: &mut Criterion) {
+    let pairs = inputs();
+
+    let mut group = c.benchmark_group("1k pairs of 40");
+    group.bench_function("LcsTable::diff", |bencher| {
+        bencher.iter(|| {
+            pairs.iter().map(|(a, b)| LcsTable::new(a, b).diff().len()).sum::<usize>()
+        })
+    });
+    group.bench_function("LcsWorkspace::diff_into", |bencher| {
+        let mut workspace = LcsWorkspace::new();
+        let mut diff = Vec::new();
+
+        bencher.iter(|| {
+            pairs.iter()
+                .map(|(a, b)| {
+                    workspace.diff_into(a, b, &mut diff);
+                    diff.len()
+                })
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_many_pairs);
+criterion_main!(benches);
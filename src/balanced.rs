@@ -0,0 +1,278 @@
+//! Diffs found by backtracking in either direction through the table, and picking between them.
+
+use {fill, Diff, DiffComponent, DiffType, LcsTable};
+use prelude::*;
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Computes a diff from `a` to `b` by backtracking from the end of both sequences. This is the
+    /// same as `diff`, and is here for symmetry with `diff_backward`.
+    pub fn diff_forward(&self) -> Diff<'_, T> {
+        self.diff()
+    }
+
+    /// Computes a diff from `a` to `b` by working from the start of both sequences instead of the
+    /// end. The two have the same number of insertions and deletions, but where there's a choice
+    /// of which elements to match, `diff_forward` matches them as late as possible, and this as
+    /// early as possible. Changes then tend to end up at the start with `diff_forward`, and at the
+    /// end with this.
+    ///
+    /// This builds a second table the size of this one, going the other way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "ab".chars().collect();
+    /// let b: Vec<_> = "abab".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_forward(), vec![
+    ///     DiffComponent::Insertion(&'a'),
+    ///     DiffComponent::Insertion(&'b'),
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Unchanged(&'b', &'b')
+    /// ]);
+    /// assert_eq!(table.diff_backward(), vec![
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Unchanged(&'b', &'b'),
+    ///     DiffComponent::Insertion(&'a'),
+    ///     DiffComponent::Insertion(&'b')
+    /// ]);
+    /// ```
+    pub fn diff_backward(&self) -> Diff<'_, T> {
+        let (n, m) = (self.a.len(), self.b.len());
+
+        // Filling a table over `a` and `b` reversed gives the length of the longest common
+        // subsequence of each pair of suffixes.
+        let reversed = fill(n, m, |i, j| self.elems_eq(n - 1 - i, m - 1 - j));
+        let suffix = |i: usize, j: usize| reversed[(n - i) * (m + 1) + m - j];
+
+        let mut diff = Vec::with_capacity(n + m - self.length());
+        let (mut i, mut j) = (0, 0);
+
+        while i < n || j < m {
+            if i == n {
+                diff.push(DiffComponent::Insertion(&self.b[j]));
+                j += 1;
+            } else if j == m {
+                diff.push(DiffComponent::Deletion(&self.a[i]));
+                i += 1;
+            } else if self.elems_eq(i, j) {
+                diff.push(DiffComponent::Unchanged(&self.a[i], &self.b[j]));
+                i += 1;
+                j += 1;
+            } else if suffix(i, j + 1) > suffix(i + 1, j) {
+                diff.push(DiffComponent::Insertion(&self.b[j]));
+                j += 1;
+            } else {
+                diff.push(DiffComponent::Deletion(&self.a[i]));
+                i += 1;
+            }
+        }
+
+        Diff::from(diff)
+    }
+
+    /// Computes both `diff_forward` and `diff_backward`, and returns whichever groups its changes
+    /// into fewer runs of consecutive insertions and deletions. Fewer, larger runs are usually
+    /// easier to read. On a tie, this returns `diff_forward`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "xaby".chars().collect();
+    /// let b: Vec<_> = "ab".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_balanced(), table.diff_forward());
+    /// ```
+    pub fn diff_balanced(&self) -> Diff<'_, T> {
+        let forward = self.diff_forward();
+        let backward = self.diff_backward();
+
+        if change_runs(&backward) < change_runs(&forward) {
+            backward
+        } else {
+            forward
+        }
+    }
+
+    /// Computes a diff from `a` to `b` with as few runs of consecutive insertions and deletions
+    /// as possible, among all the diffs with the fewest insertions and deletions. Where
+    /// `diff_balanced` only picks the better of two diffs, this considers every way of matching
+    /// up a longest common subsequence, so changes that other diffs shred into many small hunks
+    /// come out in as few as can be.
+    ///
+    /// This fills a second table the size of this one, counting the fewest runs needed to reach
+    /// each entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "ab".chars().collect();
+    /// let b: Vec<_> = "bab".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_min_hunks(), vec![
+    ///     DiffComponent::Insertion(&'b'),
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Unchanged(&'b', &'b')
+    /// ]);
+    /// ```
+    pub fn diff_min_hunks(&self) -> Diff<'_, T> {
+        let (n, m) = (self.a.len(), self.b.len());
+        let width = m + 1;
+
+        // `runs[(i * width + j) * 2 + after]` is the fewest runs of changes in a diff from
+        // `a[..i]` to `b[..j]` that still leads to a minimal diff overall, where `after` is 1 if
+        // the component following that diff is a change, which a change at its end would join.
+        let mut runs = vec![0; (n + 1) * width * 2];
+        for i in 0..n + 1 {
+            for j in 0..m + 1 {
+                for after in 0..2 {
+                    if let Some((_, count)) = self.min_hunks_step(&runs, i, j, after) {
+                        runs[(i * width + j) * 2 + after] = count;
+                    }
+                }
+            }
+        }
+
+        let mut steps = Vec::with_capacity(n + m);
+        let (mut i, mut j, mut after) = (n, m, 0);
+
+        while let Some((step, _)) = self.min_hunks_step(&runs, i, j, after) {
+            match step {
+                DiffType::Insertion => j -= 1,
+                DiffType::Unchanged => { i -= 1; j -= 1; },
+                DiffType::Deletion => i -= 1
+            }
+
+            after = !matches!(step, DiffType::Unchanged) as usize;
+            steps.push(step);
+        }
+
+        self.components(steps).collect()
+    }
+
+    /// Finds the step back from `(i, j)` that leads to the fewest runs of changes, given the
+    /// counts in `runs` for the entries before it, or `None` if `(i, j)` is the start. Only steps
+    /// that keep the diff minimal are considered: matches, and insertions and deletions that
+    /// don't shorten the longest common subsequence. On a tie, matches come first, and deletions
+    /// before insertions, as with `diff`.
+    fn min_hunks_step(&self, runs: &[usize], i: usize, j: usize, after: usize)
+            -> Option<(DiffType, usize)> {
+        let width = self.b.len() + 1;
+        let runs_at = |i: usize, j: usize, after: usize| runs[(i * width + j) * 2 + after];
+        let change_cost = 1 - after;
+
+        let mut best: Option<(DiffType, usize)> = None;
+        let mut consider = |step: DiffType, count: usize| {
+            if best.map_or(true, |(_, best_count)| count < best_count) {
+                best = Some((step, count));
+            }
+        };
+
+        if i > 0 && j > 0 && self.elems_eq(i - 1, j - 1) {
+            consider(DiffType::Unchanged, runs_at(i - 1, j - 1, 0));
+        }
+
+        if i > 0 && self.cell(i - 1, j) == self.cell(i, j) {
+            consider(DiffType::Deletion, runs_at(i - 1, j, 1) + change_cost);
+        }
+
+        if j > 0 && self.cell(i, j - 1) == self.cell(i, j) {
+            consider(DiffType::Insertion, runs_at(i, j - 1, 1) + change_cost);
+        }
+
+        best
+    }
+}
+
+/// Counts the maximal runs of insertions and deletions in `diff`.
+fn change_runs<T>(diff: &[DiffComponent<T>]) -> usize {
+    let is_change = |component: &DiffComponent<T>| {
+        !matches!(*component, DiffComponent::Unchanged(_, _))
+    };
+
+    diff.iter().enumerate()
+        .filter(|&(k, component)| is_change(component) && (k == 0 || !is_change(&diff[k - 1])))
+        .count()
+}
+
+#[test]
+fn test_diff_backward() {
+    use {apply, random_strings};
+
+    for (a, b) in random_strings(0x3c6e_f372_fe94_f82b, 300) {
+        let table = LcsTable::new(&a, &b);
+        let backward = table.diff_backward();
+
+        assert_eq!(table.diff_stats(), backward.stats());
+        assert_eq!(Ok(b.clone()), apply(&a, &backward));
+
+        // Working from the start of the reversed inputs is the same as working from the end of
+        // the originals.
+        let reversed_a: Vec<_> = a.iter().rev().cloned().collect();
+        let reversed_b: Vec<_> = b.iter().rev().cloned().collect();
+        let reversed = LcsTable::new(&reversed_a, &reversed_b);
+
+        let mut forward = table.diff_forward().into_vec();
+        forward.reverse();
+        assert_eq!(reversed.diff_backward(), forward);
+    }
+}
+
+#[test]
+fn test_diff_min_hunks() {
+    use {apply, random_strings};
+
+    for (a, b) in random_strings(0x1f83_d9ab_fb41_bd6b, 300) {
+        let table = LcsTable::new(&a, &b);
+        let diff = table.diff_min_hunks();
+
+        assert_eq!(table.diff_stats(), diff.stats());
+        assert_eq!(Ok(b.clone()), apply(&a, &diff));
+        assert!(change_runs(&diff) <= change_runs(&table.diff_balanced()));
+    }
+
+    // Matching the first "a" early and the second one late keeps the insertions together, which
+    // neither direction of backtracking does on its own.
+    let a: Vec<_> = "aa".chars().collect();
+    let b: Vec<_> = "abbaba".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(change_runs(&table.diff_balanced()), 2);
+    assert_eq!(table.diff_min_hunks(), vec![
+        DiffComponent::Unchanged(&'a', &'a'),
+        DiffComponent::Insertion(&'b'),
+        DiffComponent::Insertion(&'b'),
+        DiffComponent::Insertion(&'a'),
+        DiffComponent::Insertion(&'b'),
+        DiffComponent::Unchanged(&'a', &'a')
+    ]);
+}
+
+#[test]
+fn test_diff_balanced() {
+    use random_strings;
+
+    for (a, b) in random_strings(0xa54f_f53a_5f1d_36f1, 300) {
+        let table = LcsTable::new(&a, &b);
+        let runs = change_runs(&table.diff_balanced());
+
+        assert!(runs <= change_runs(&table.diff_forward()));
+        assert!(runs <= change_runs(&table.diff_backward()));
+    }
+
+    // Changes split around the match one way, and grouped the other.
+    let a: Vec<_> = "ab".chars().collect();
+    let b: Vec<_> = "bab".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(change_runs(&table.diff_balanced()), 1);
+}
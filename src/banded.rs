@@ -0,0 +1,193 @@
+//! Longest common subsequences where matched elements can't be too far apart.
+
+use std::cmp;
+
+use {Diff, DiffComponent, LcsTable};
+use prelude::*;
+
+/// Like an `LcsTable`, but only allowing matches between elements whose positions are close to
+/// each other. See `LcsTable::new_banded`.
+#[derive(Debug, Clone)]
+pub struct BandedLcsTable<'a, T: 'a> {
+    // Row `i` holds the entries for `j` from `i - band` to `i + band`, so the entry for `a[..i]`
+    // and `b[..j]` is at `i * (2 * band + 1) + j + band - i`. Entries outside of the table are
+    // left as zero and never read.
+    lengths: Vec<usize>,
+    band: usize,
+
+    a: &'a [T],
+    b: &'a [T]
+}
+
+impl<'a, T> LcsTable<'a, T> {
+    /// Constructs a table for finding the longest common subsequence of `a` and `b` where every
+    /// matched pair `(a[i], b[j])` has `i` and `j` at most `band` apart. This is useful for
+    /// aligning sequences that are expected to line up roughly position by position, like two
+    /// logs of the same run, where a match far from the diagonal is more likely a coincidence.
+    ///
+    /// Only the entries within `band` of the diagonal are filled, so this takes `O(a.len() *
+    /// band)` time and space rather than `O(a.len() * b.len())`. The result is the best alignment
+    /// within the band, which may be shorter than the longest common subsequence overall. With a
+    /// `band` of at least `max(a.len(), b.len())`, they're the same.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abcxyz".chars().collect();
+    /// let b: Vec<_> = "xyzabc".chars().collect();
+    ///
+    /// assert_eq!(3, LcsTable::new(&a, &b).length());
+    /// assert_eq!(0, LcsTable::new_banded(&a, &b, 2).length());
+    /// assert_eq!(3, LcsTable::new_banded(&a, &b, 3).length());
+    /// ```
+    pub fn new_banded(a: &'a [T], b: &'a [T], band: usize) -> BandedLcsTable<'a, T>
+            where T: Eq {
+        let band = cmp::min(band, cmp::max(a.len(), b.len()));
+        let mut table = BandedLcsTable {
+            lengths: vec![0; (a.len() + 1) * (2 * band + 1)],
+            band,
+            a,
+            b
+        };
+
+        for i in 1..a.len() + 1 {
+            for j in cmp::max(1, i.saturating_sub(band))..cmp::min(b.len(), i + band) + 1 {
+                let length = if a[i - 1] == b[j - 1] {
+                    1 + table.get(i - 1, j - 1)
+                } else {
+                    cmp::max(table.get(i, j - 1), table.get(i - 1, j))
+                };
+
+                let index = table.index(i, j);
+                table.lengths[index] = length;
+            }
+        }
+
+        table
+    }
+}
+
+impl<'a, T> BandedLcsTable<'a, T> where T: Eq {
+    /// Gets the length of the longest common subsequence between `a` and `b` whose matches are
+    /// all within the band.
+    pub fn length(&self) -> usize {
+        self.get(self.a.len(), self.b.len())
+    }
+
+    /// Gets the longest common subsequence within the band, in the same form as
+    /// `LcsTable::longest_common_subsequence`.
+    pub fn longest_common_subsequence(&self) -> Vec<(&T, &T)> {
+        self.diff().into_iter()
+            .filter_map(|component| match component {
+                DiffComponent::Unchanged(elem_a, elem_b) => Some((elem_a, elem_b)),
+                _ => None
+            })
+            .collect()
+    }
+
+    /// Computes a diff from `a` to `b` whose unchanged elements are the longest common
+    /// subsequence within the band. Ties are broken the same way as `LcsTable::diff`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "ab".chars().collect();
+    /// let b: Vec<_> = "xab".chars().collect();
+    ///
+    /// let table = LcsTable::new_banded(&a, &b, 0);
+    /// assert_eq!(table.diff(), vec![
+    ///     DiffComponent::Insertion(&'x'),
+    ///     DiffComponent::Insertion(&'a'),
+    ///     DiffComponent::Insertion(&'b'),
+    ///     DiffComponent::Deletion(&'a'),
+    ///     DiffComponent::Deletion(&'b')
+    /// ]);
+    ///
+    /// let table = LcsTable::new_banded(&a, &b, 1);
+    /// assert_eq!(table.longest_common_subsequence(), vec![(&'a', &'a'), (&'b', &'b')]);
+    /// ```
+    pub fn diff(&self) -> Diff<'a, T> {
+        let mut diff = Vec::with_capacity(self.a.len() + self.b.len());
+        let (mut i, mut j) = (self.a.len(), self.b.len());
+
+        while i > 0 || j > 0 {
+            if i == 0 {
+                diff.push(DiffComponent::Insertion(&self.b[j - 1]));
+                j -= 1;
+            } else if j == 0 {
+                diff.push(DiffComponent::Deletion(&self.a[i - 1]));
+                i -= 1;
+            } else if self.in_band(i, j) && self.a[i - 1] == self.b[j - 1] {
+                diff.push(DiffComponent::Unchanged(&self.a[i - 1], &self.b[j - 1]));
+                i -= 1;
+                j -= 1;
+            } else if self.get(i, j - 1) > self.get(i - 1, j) {
+                diff.push(DiffComponent::Insertion(&self.b[j - 1]));
+                j -= 1;
+            } else {
+                diff.push(DiffComponent::Deletion(&self.a[i - 1]));
+                i -= 1;
+            }
+        }
+
+        diff.into_iter().rev().collect()
+    }
+
+    fn in_band(&self, i: usize, j: usize) -> bool {
+        j <= i + self.band && i <= j + self.band
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * (2 * self.band + 1) + j + self.band - i
+    }
+
+    fn get(&self, i: usize, j: usize) -> usize {
+        // Nothing outside the band can be matched, so an entry past either edge of the band is
+        // the same as the one on the edge: moving further from the band can't add any matches.
+        if j > i + self.band {
+            self.get(i, i + self.band)
+        } else if i > j + self.band {
+            self.get(j + self.band, j)
+        } else {
+            self.lengths[self.index(i, j)]
+        }
+    }
+}
+
+#[test]
+fn test_new_banded() {
+    use random_strings;
+
+    for (a, b) in random_strings(0xe3a8_1f5c_9b46_d027, 200) {
+        let full = LcsTable::new(&a, &b);
+        let wide = LcsTable::new_banded(&a, &b, usize::MAX);
+        assert_eq!(full.diff(), wide.diff());
+
+        for band in 0..4 {
+            let table = LcsTable::new_banded(&a, &b, band);
+            let diff = table.diff();
+            assert!(table.length() <= full.length());
+
+            let (mut i, mut j, mut matched) = (0, 0, 0);
+            for component in &diff {
+                match *component {
+                    DiffComponent::Insertion(_) => j += 1,
+                    DiffComponent::Unchanged(elem_a, elem_b) => {
+                        assert_eq!(elem_a, elem_b);
+                        assert!(cmp::max(i, j) - cmp::min(i, j) <= band);
+                        i += 1;
+                        j += 1;
+                        matched += 1;
+                    },
+                    DiffComponent::Deletion(_) => i += 1
+                }
+            }
+
+            assert_eq!((i, j, matched), (a.len(), b.len(), table.length()));
+        }
+    }
+}
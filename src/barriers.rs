@@ -0,0 +1,99 @@
+//! Diffing sequences split into segments that matches can't cross.
+
+use {Diff, LcsTable};
+use prelude::*;
+
+impl<'a, T> LcsTable<'a, T> where T: Eq {
+    /// Computes a diff from `a` to `b` where the positions in `barriers_a` and `barriers_b` split
+    /// the sequences into segments, and elements can only match within corresponding segments.
+    /// The `k`th barrier of `a` lines up with the `k`th barrier of `b`, so each segment is diffed
+    /// on its own, and the diffs are concatenated. This keeps structured input, like the tokens
+    /// between a pair of brackets, from being matched up with some other part of the input.
+    ///
+    /// A barrier at `k` splits a sequence just before its `k`th element.
+    ///
+    /// Panics if `barriers_a` and `barriers_b` have different lengths, or if either isn't sorted
+    /// or has a position past the end of its sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "ab|c".chars().collect();
+    /// let b: Vec<_> = "c|ab".chars().collect();
+    ///
+    /// // Without barriers, "ab" is matched across the `|`.
+    /// assert_eq!(2, LcsTable::new(&a, &b).length());
+    ///
+    /// let diff = LcsTable::with_barriers(&a, &b, &[2], &[1]);
+    /// assert_eq!(diff, vec![
+    ///     DiffComponent::Insertion(&'c'),
+    ///     DiffComponent::Deletion(&'a'),
+    ///     DiffComponent::Deletion(&'b'),
+    ///     DiffComponent::Unchanged(&'|', &'|'),
+    ///     DiffComponent::Insertion(&'a'),
+    ///     DiffComponent::Insertion(&'b'),
+    ///     DiffComponent::Deletion(&'c')
+    /// ]);
+    /// ```
+    pub fn with_barriers(a: &'a [T], b: &'a [T], barriers_a: &[usize], barriers_b: &[usize])
+            -> Diff<'a, T> {
+        assert_eq!(barriers_a.len(), barriers_b.len(), "sequences have different barrier counts");
+
+        let bounds = |seq: &[T], barriers: &[usize]| -> Vec<usize> {
+            let mut bounds = vec![0];
+            bounds.extend_from_slice(barriers);
+            bounds.push(seq.len());
+
+            assert!(bounds.windows(2).all(|pair| pair[0] <= pair[1]),
+                    "barriers aren't sorted or are out of range");
+            bounds
+        };
+
+        let bounds_a = bounds(a, barriers_a);
+        let bounds_b = bounds(b, barriers_b);
+
+        let mut diff = Vec::with_capacity(a.len() + b.len());
+        for (range_a, range_b) in bounds_a.windows(2).zip(bounds_b.windows(2)) {
+            let segment_a = &a[range_a[0]..range_a[1]];
+            let segment_b = &b[range_b[0]..range_b[1]];
+            diff.extend(LcsTable::new(segment_a, segment_b).diff_iter());
+        }
+
+        Diff::from(diff)
+    }
+}
+
+#[test]
+fn test_with_barriers() {
+    use {apply, random_strings};
+
+    for (a, b) in random_strings(0x510e_527f_ade6_82d1, 200) {
+        // Split both at the same fractions of their lengths.
+        let barriers_a = [a.len() / 3, a.len() / 2];
+        let barriers_b = [b.len() / 3, b.len() / 2];
+        let diff = LcsTable::with_barriers(&a, &b, &barriers_a, &barriers_b);
+
+        assert_eq!(Ok(b.clone()), apply(&a, &diff));
+
+        let segments = |seq: &[char], barriers: &[usize; 2]| {
+            vec![seq[..barriers[0]].to_vec(), seq[barriers[0]..barriers[1]].to_vec(),
+                 seq[barriers[1]..].to_vec()]
+        };
+        let unchanged: usize = segments(&a, &barriers_a).iter().zip(segments(&b, &barriers_b))
+            .map(|(segment_a, segment_b)| LcsTable::new(segment_a, &segment_b).length())
+            .sum();
+        assert_eq!(unchanged, diff.stats().unchanged);
+
+        let without_barriers = LcsTable::with_barriers(&a, &b, &[], &[]);
+        assert_eq!(LcsTable::new(&a, &b).diff(), without_barriers);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_with_barriers_mismatched() {
+    let a: Vec<_> = "abc".chars().collect();
+    LcsTable::with_barriers(&a, &a, &[1], &[]);
+}
@@ -0,0 +1,127 @@
+//! Telling changes that matter apart from ones that don't, such as whitespace-only changes.
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// What kind of change a diff component is part of. See `LcsTable::diff_classified`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeClass {
+    /// The component is `Unchanged`.
+    Unchanged,
+
+    /// The component is an insertion or deletion, in a run of changes made up only of ignorable
+    /// elements.
+    Ignorable,
+
+    /// The component is an insertion or deletion, in a run of changes with at least one element
+    /// that isn't ignorable.
+    Substantive
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Computes a diff from `a` to `b` like `diff`, with each component tagged by the kind of
+    /// change it's part of. Consecutive insertions and deletions form a run of changes, and a run
+    /// is `ChangeClass::Ignorable` if `is_ignorable` holds for every element in it. Otherwise, all
+    /// of its components are `ChangeClass::Substantive`.
+    ///
+    /// With `char::is_whitespace` as `is_ignorable`, this picks out the whitespace-only changes of a
+    /// character diff, so they can be shown dimmed in a code review.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{ChangeClass, LcsTable};
+    ///
+    /// let a: Vec<_> = "a b".chars().collect();
+    /// let b: Vec<_> = "a  c".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let classes: Vec<_> = table.diff_classified(|c| c.is_whitespace()).into_iter()
+    ///     .map(|(_, class)| class)
+    ///     .collect();
+    ///
+    /// assert_eq!(classes, vec![
+    ///     ChangeClass::Unchanged,
+    ///     ChangeClass::Ignorable,
+    ///     ChangeClass::Unchanged,
+    ///     ChangeClass::Substantive,
+    ///     ChangeClass::Substantive
+    /// ]);
+    /// ```
+    pub fn diff_classified<F>(&self, is_ignorable: F) -> Vec<(DiffComponent<&T>, ChangeClass)>
+            where F: Fn(&T) -> bool {
+        let mut classified: Vec<_> = self.diff_iter()
+            .map(|component| (component, ChangeClass::Unchanged))
+            .collect();
+
+        let mut start = 0;
+        while start < classified.len() {
+            if classified[start].0.is_unchanged() {
+                start += 1;
+                continue;
+            }
+
+            let len = classified[start..].iter()
+                .take_while(|(component, _)| !component.is_unchanged())
+                .count();
+            let run = &mut classified[start..start + len];
+
+            let class = if run.iter().all(|(component, _)| is_ignorable(component.elem())) {
+                ChangeClass::Ignorable
+            } else {
+                ChangeClass::Substantive
+            };
+
+            for (_, component_class) in run {
+                *component_class = class;
+            }
+
+            start += len;
+        }
+
+        classified
+    }
+}
+
+impl<'t, T> DiffComponent<&'t T> {
+    fn is_unchanged(&self) -> bool {
+        matches!(*self, DiffComponent::Unchanged(_, _))
+    }
+
+    /// The inserted or deleted element, or the element from `a` if unchanged.
+    fn elem(&self) -> &'t T {
+        match *self {
+            DiffComponent::Insertion(elem) => elem,
+            DiffComponent::Unchanged(elem, _) => elem,
+            DiffComponent::Deletion(elem) => elem
+        }
+    }
+}
+
+#[test]
+fn test_diff_classified() {
+    let a: Vec<_> = "fn f() {\n  x\n}".chars().collect();
+    let b: Vec<_> = "fn f()  {\n    y\n}".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let classified = table.diff_classified(|c| c.is_whitespace());
+
+    let components: Vec<_> = classified.iter()
+        .map(|(component, _)| match *component {
+            DiffComponent::Insertion(elem) => DiffComponent::Insertion(elem),
+            DiffComponent::Unchanged(elem_a, elem_b) => DiffComponent::Unchanged(elem_a, elem_b),
+            DiffComponent::Deletion(elem) => DiffComponent::Deletion(elem)
+        })
+        .collect();
+    assert_eq!(table.diff(), components);
+
+    let changed = |class: ChangeClass| -> String {
+        classified.iter()
+            .filter(|&&(_, component_class)| component_class == class)
+            .map(|(component, _)| *component.elem())
+            .collect()
+    };
+
+    assert_eq!("   ", changed(ChangeClass::Ignorable));
+    assert_eq!("yx", changed(ChangeClass::Substantive));
+}
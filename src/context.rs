@@ -0,0 +1,169 @@
+//! Grouping a diff into changes with the unchanged elements around them.
+
+use std::{cmp, iter};
+
+use {Diff, DiffComponent, LcsTable};
+use prelude::*;
+
+/// A run of changes in a diff, along with up to some number of unchanged elements on either side
+/// of it, as in a hunk of a unified diff. See `LcsTable::contextual`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ContextBlock<T> {
+    /// The index in `a` of the first element of the block, including its leading context.
+    pub a_start: usize,
+
+    /// The index in `b` of the first element of the block, including its leading context.
+    pub b_start: usize,
+
+    /// The unchanged elements before the first change.
+    pub leading: Vec<DiffComponent<T>>,
+
+    /// Everything from the first change to the last one. Where the contexts of nearby changes
+    /// overlapped, this includes the unchanged elements between them.
+    pub changes: Vec<DiffComponent<T>>,
+
+    /// The unchanged elements after the last change.
+    pub trailing: Vec<DiffComponent<T>>
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Groups the diff from `a` to `b` into blocks of changes, each with up to `context`
+    /// unchanged elements before and after it. This is how `unified_diff` lays out its hunks,
+    /// without rendering them as text.
+    ///
+    /// Changes separated by no more than `2 * context` unchanged elements would have overlapping
+    /// contexts, so they share a block. Blocks never overlap each other, and there are none if
+    /// `a` and `b` are identical.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "abcdef".chars().collect();
+    /// let b: Vec<_> = "aBcdeF".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let blocks: Vec<_> = table.contextual(1).collect();
+    /// assert_eq!(blocks.len(), 2);
+    ///
+    /// assert_eq!((blocks[0].a_start, blocks[0].b_start), (0, 0));
+    /// assert_eq!(blocks[0].leading, vec![DiffComponent::Unchanged(&'a', &'a')]);
+    /// assert_eq!(blocks[0].changes, vec![
+    ///     DiffComponent::Insertion(&'B'),
+    ///     DiffComponent::Deletion(&'b')
+    /// ]);
+    /// assert_eq!(blocks[0].trailing, vec![DiffComponent::Unchanged(&'c', &'c')]);
+    ///
+    /// assert_eq!((blocks[1].a_start, blocks[1].b_start), (4, 4));
+    /// assert!(blocks[1].trailing.is_empty());
+    ///
+    /// // With more context, the two changes are close enough to share a block.
+    /// assert_eq!(table.contextual(2).count(), 1);
+    /// ```
+    pub fn contextual(&self, context: usize) -> impl Iterator<Item = ContextBlock<&T>> {
+        self.diff().contextual(context).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<'a, T> Diff<'a, T> {
+    /// Lazily groups the diff into blocks of changes with up to `context` unchanged elements on
+    /// either side. See `LcsTable::contextual`.
+    pub fn contextual(&self, context: usize)
+            -> impl Iterator<Item = ContextBlock<&'a T>> + '_ {
+        let diff = &self[..];
+        let mut changes = diff.iter().enumerate()
+            .filter(|&(_, component)| !matches!(*component, DiffComponent::Unchanged(_, _)))
+            .map(|(k, _)| k)
+            .peekable();
+
+        // How far into `diff`, `a`, and `b` the blocks so far have gotten.
+        let (mut k, mut i, mut j) = (0, 0, 0);
+
+        iter::from_fn(move || {
+            let first = changes.next()?;
+            let mut last = first;
+            while let Some(&next) = changes.peek() {
+                if next - last - 1 > 2 * context {
+                    break;
+                }
+
+                last = next;
+                changes.next();
+            }
+
+            // The gap before `first` is more than `2 * context`, so this is past the last block.
+            let start = first.saturating_sub(context);
+            let end = cmp::min(diff.len(), last + 1 + context);
+
+            advance(&diff[k..start], &mut i, &mut j);
+            let block = ContextBlock {
+                a_start: i,
+                b_start: j,
+                leading: diff[start..first].to_vec(),
+                changes: diff[first..last + 1].to_vec(),
+                trailing: diff[last + 1..end].to_vec()
+            };
+
+            advance(&diff[start..end], &mut i, &mut j);
+            k = end;
+            Some(block)
+        })
+    }
+}
+
+/// Moves the indices `i` into `a` and `j` into `b` past `components`.
+fn advance<T>(components: &[DiffComponent<T>], i: &mut usize, j: &mut usize) {
+    for component in components {
+        match *component {
+            DiffComponent::Insertion(_) => *j += 1,
+            DiffComponent::Unchanged(_, _) => { *i += 1; *j += 1; },
+            DiffComponent::Deletion(_) => *i += 1
+        }
+    }
+}
+
+#[test]
+fn test_contextual() {
+    use random_strings;
+
+    let unchanged = |component: &DiffComponent<&char>| {
+        matches!(*component, DiffComponent::Unchanged(_, _))
+    };
+
+    for (a, b) in random_strings(0x93d0_6c2b_4e71_fa58, 200) {
+        let table = LcsTable::new(&a, &b);
+        let diff = table.diff();
+
+        for context in 0..4 {
+            let (mut k, mut i, mut j) = (0, 0, 0);
+            let mut previous_trailing = None;
+
+            for block in table.contextual(context) {
+                assert!(block.leading.len() <= context && block.trailing.len() <= context);
+                assert!(block.leading.iter().chain(&block.trailing).all(unchanged));
+                assert!(!unchanged(&block.changes[0]));
+                assert!(!unchanged(&block.changes[block.changes.len() - 1]));
+
+                // Everything between blocks is unchanged, and too long to fit in the contexts of
+                // the blocks on either side.
+                let gap = block.a_start - i;
+                assert_eq!(gap, block.b_start - j);
+                assert!(diff[k..k + gap].iter().all(unchanged));
+                if let Some(trailing) = previous_trailing {
+                    assert!(trailing + gap + block.leading.len() > 2 * context);
+                }
+
+                let all: Vec<_> = block.leading.iter().chain(&block.changes)
+                    .chain(&block.trailing).cloned().collect();
+                assert_eq!(&diff[k + gap..k + gap + all.len()], &all[..]);
+
+                advance(&diff[k..k + gap + all.len()], &mut i, &mut j);
+                k += gap + all.len();
+                previous_trailing = Some(block.trailing.len());
+            }
+
+            assert!(diff[k..].iter().all(unchanged));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Picking, among all longest common subsequences, one whose matches are as contiguous as possible.
+
+use std::cmp;
+
+use LcsTable;
+use prelude::*;
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Gets a longest common subsequence between `a` and `b` made of as few runs as possible,
+    /// where a run is a stretch of matches that are adjacent in both `a` and `b`. Returned elements
+    /// are in the same form as `longest_common_subsequence`.
+    ///
+    /// The result has the same length as `longest_common_subsequence`; only the choice between
+    /// several longest common subsequences differs. Preferring long runs tends to give diffs that
+    /// keep related elements together, which reads better for structured data. This needs two more
+    /// tables the size of this one.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abxb".chars().collect();
+    /// let b: Vec<_> = "ab".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.subsequence_indices(), vec![(0, 0), (3, 1)]);
+    ///
+    /// let lcs = table.longest_common_subsequence_contiguous();
+    /// assert!(std::ptr::eq(lcs[1].0, &a[1]));
+    /// ```
+    pub fn longest_common_subsequence_contiguous(&self) -> Vec<(&T, &T)> {
+        let (n, m) = (self.a.len(), self.b.len());
+
+        // `any_runs[at(i, j)]` is the fewest runs in a longest common subsequence of `a[..i]` and
+        // `b[..j]`, and `ending_runs[at(i, j)]` is the same but only counting subsequences that end
+        // by matching `a[i - 1]` with `b[j - 1]`. The latter is only meaningful where those are
+        // equal. Both are laid out like the table itself.
+        let at = |i: usize, j: usize| i * (m + 1) + j;
+        let mut any_runs = vec![0; (n + 1) * (m + 1)];
+        let mut ending_runs = vec![0; (n + 1) * (m + 1)];
+
+        for i in 1..n + 1 {
+            for j in 1..m + 1 {
+                let mut best = usize::MAX;
+
+                if self.matches(i, j) {
+                    ending_runs[at(i, j)] = if self.matches(i - 1, j - 1) {
+                        cmp::min(ending_runs[at(i - 1, j - 1)], any_runs[at(i - 1, j - 1)] + 1)
+                    } else {
+                        any_runs[at(i - 1, j - 1)] + 1
+                    };
+
+                    best = ending_runs[at(i, j)];
+                }
+
+                if self.cell(i - 1, j) == self.cell(i, j) {
+                    best = cmp::min(best, any_runs[at(i - 1, j)]);
+                }
+
+                if self.cell(i, j - 1) == self.cell(i, j) {
+                    best = cmp::min(best, any_runs[at(i, j - 1)]);
+                }
+
+                any_runs[at(i, j)] = best;
+            }
+        }
+
+        let mut lcs = Vec::with_capacity(self.length());
+        let (mut i, mut j) = (n, m);
+        let mut ending = false;
+
+        while i > 0 && j > 0 {
+            if ending || (self.matches(i, j) && ending_runs[at(i, j)] == any_runs[at(i, j)]) {
+                lcs.push((&self.a[i - 1], &self.b[j - 1]));
+                ending = self.matches(i - 1, j - 1) &&
+                    ending_runs[at(i - 1, j - 1)] == ending_runs[at(i, j)];
+                i -= 1;
+                j -= 1;
+            } else if self.cell(i - 1, j) == self.cell(i, j) &&
+                    any_runs[at(i - 1, j)] == any_runs[at(i, j)] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+
+        lcs.reverse();
+        lcs
+    }
+
+    /// Whether `a[i - 1]` and `b[j - 1]` exist and are equal.
+    fn matches(&self, i: usize, j: usize) -> bool {
+        i > 0 && j > 0 && self.elems_eq(i - 1, j - 1)
+    }
+}
+
+#[test]
+fn test_contiguous_has_fewest_runs() {
+    use random_strings;
+
+    // Collects every common subsequence of `a[start.0..]` and `b[start.1..]`, as index pairs
+    // following on from `current`.
+    fn alignments(a: &[char], b: &[char], start: (usize, usize), current: &mut Vec<(usize, usize)>,
+                  all: &mut Vec<Vec<(usize, usize)>>) {
+        all.push(current.clone());
+
+        for i in start.0..a.len() {
+            for j in start.1..b.len() {
+                if a[i] == b[j] {
+                    current.push((i, j));
+                    alignments(a, b, (i + 1, j + 1), current, all);
+                    current.pop();
+                }
+            }
+        }
+    }
+
+    for (a, b) in random_strings(0x1ce4_e5b9_bf58_476d, 300) {
+        let table = LcsTable::new(&a, &b);
+        let lcs = table.longest_common_subsequence_contiguous();
+        assert_eq!(table.length(), lcs.len());
+
+        let index = |elem: &char, seq: &[char]| {
+            seq.iter().position(|x| ::std::ptr::eq(x, elem)).unwrap()
+        };
+
+        let indices: Vec<_> = lcs.iter()
+            .map(|&(elem_a, elem_b)| (index(elem_a, &a), index(elem_b, &b)))
+            .collect();
+
+        // Counts the runs in a subsequence given as index pairs into `a` and `b`.
+        let runs = |indices: &[(usize, usize)]| {
+            indices.len() - indices.windows(2)
+                .filter(|pair| pair[1] == (pair[0].0 + 1, pair[0].1 + 1))
+                .count()
+        };
+
+        for window in indices.windows(2) {
+            assert!(window[0].0 < window[1].0 && window[0].1 < window[1].1);
+        }
+
+        for &(i, j) in &indices {
+            assert_eq!(a[i], b[j]);
+        }
+
+        if a.len() <= 8 && b.len() <= 8 {
+            let mut all = Vec::new();
+            alignments(&a, &b, (0, 0), &mut Vec::new(), &mut all);
+
+            let fewest = all.iter()
+                .filter(|other| other.len() == table.length())
+                .map(|other| runs(other))
+                .min()
+                .unwrap();
+            assert_eq!(fewest, runs(&indices));
+        }
+    }
+
+    let a: Vec<_> = "abxb".chars().collect();
+    let b: Vec<_> = "ab".chars().collect();
+    let table = LcsTable::new(&a, &b);
+    let lcs = table.longest_common_subsequence_contiguous();
+    assert!(::std::ptr::eq(lcs[0].0, &a[0]) && ::std::ptr::eq(lcs[1].0, &a[1]));
+}
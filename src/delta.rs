@@ -0,0 +1,224 @@
+//! Describing a diff compactly, as ranges to copy from `a` and new elements to insert.
+
+#[cfg(feature = "std")]
+use std::io;
+
+use {DiffComponent, LcsTable, PatchError};
+use prelude::*;
+
+/// An operation in a copy/insert encoding of a diff, from `LcsTable::to_copy_insert`. Carrying
+/// out each operation in turn, appending its elements to an empty sequence, builds `b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CopyInsertOp<T> {
+    /// Append the `len` elements of `a` starting at `src_start`.
+    Copy { src_start: usize, len: usize },
+
+    /// Append these new elements.
+    Insert(Vec<T>)
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: Clone, E: Fn(&T, &T) -> bool {
+    /// Encodes the diff from `a` to `b` as operations that copy runs of unchanged elements out of
+    /// `a` and insert the rest. Deleted elements are simply never copied. Every unchanged run
+    /// becomes one `Copy`, and every run of insertions one `Insert`, so this is much smaller than
+    /// the diff itself when `a` and `b` are long and mostly the same, such as for sending `b` to
+    /// someone who already has `a`. `apply_copy_insert` turns `a` back into `b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{apply_copy_insert, CopyInsertOp, LcsTable};
+    ///
+    /// let a: Vec<_> = "hello world".chars().collect();
+    /// let b: Vec<_> = "hello, world!".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let ops = table.to_copy_insert();
+    /// assert_eq!(ops, vec![
+    ///     CopyInsertOp::Copy { src_start: 0, len: 5 },
+    ///     CopyInsertOp::Insert(vec![',']),
+    ///     CopyInsertOp::Copy { src_start: 5, len: 6 },
+    ///     CopyInsertOp::Insert(vec!['!'])
+    /// ]);
+    /// assert_eq!(apply_copy_insert(&a, &ops), Ok(b));
+    /// ```
+    pub fn to_copy_insert(&self) -> Vec<CopyInsertOp<T>> {
+        let mut ops = Vec::new();
+        let mut i = 0;
+
+        for component in self.diff_iter() {
+            match component {
+                DiffComponent::Insertion(elem_b) => {
+                    if let Some(&mut CopyInsertOp::Insert(ref mut elems)) = ops.last_mut() {
+                        elems.push(elem_b.clone());
+                    } else {
+                        ops.push(CopyInsertOp::Insert(vec![elem_b.clone()]));
+                    }
+                },
+
+                DiffComponent::Unchanged(_, _) => {
+                    // A deletion since the last copy means this one can't continue it.
+                    match ops.last_mut() {
+                        Some(&mut CopyInsertOp::Copy { src_start, ref mut len })
+                                if src_start + *len == i => *len += 1,
+                        _ => ops.push(CopyInsertOp::Copy { src_start: i, len: 1 })
+                    }
+
+                    i += 1;
+                },
+
+                DiffComponent::Deletion(_) => i += 1
+            }
+        }
+
+        ops
+    }
+}
+
+/// Carries out `ops` from `LcsTable::to_copy_insert`, copying from `a`, to build the sequence they
+/// encode. If a `Copy` reaches past the end of `a`, this returns `PatchError::OutOfBounds` with
+/// the index in `a` it starts at.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{apply_copy_insert, CopyInsertOp, PatchError};
+///
+/// let a = vec![1, 2, 3];
+/// let ops = vec![CopyInsertOp::Copy { src_start: 1, len: 2 }, CopyInsertOp::Insert(vec![4])];
+/// assert_eq!(apply_copy_insert(&a, &ops), Ok(vec![2, 3, 4]));
+///
+/// let ops = vec![CopyInsertOp::Copy { src_start: 2, len: 2 }];
+/// assert_eq!(apply_copy_insert(&a, &ops), Err(PatchError::OutOfBounds { index: 2 }));
+/// ```
+pub fn apply_copy_insert<T>(a: &[T], ops: &[CopyInsertOp<T>]) -> Result<Vec<T>, PatchError>
+        where T: Clone {
+    let mut b = Vec::new();
+    apply_copy_insert_into(a, ops, &mut b)?;
+    Ok(b)
+}
+
+/// Like `apply_copy_insert`, but appends the sequence `ops` encode to `out` one run at a time,
+/// rather than collecting it. `out` can be anything that can be extended, such as a `String` for
+/// a sequence of characters.
+///
+/// Runs are appended as they're reached, so if a `Copy` reaches past the end of `a`, everything
+/// before it has already been appended when this returns the error.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{apply_copy_insert_into, LcsTable};
+///
+/// let a: Vec<_> = "hello world".chars().collect();
+/// let b: Vec<_> = "hello, world!".chars().collect();
+/// let ops = LcsTable::new(&a, &b).to_copy_insert();
+///
+/// let mut out = String::from("> ");
+/// apply_copy_insert_into(&a, &ops, &mut out).unwrap();
+/// assert_eq!(out, "> hello, world!");
+/// ```
+pub fn apply_copy_insert_into<T, W>(a: &[T], ops: &[CopyInsertOp<T>], out: &mut W)
+        -> Result<(), PatchError> where T: Clone, W: Extend<T> {
+    for op in ops {
+        match *op {
+            CopyInsertOp::Copy { src_start, len } => {
+                out.extend(copied_run(a, src_start, len)?.iter().cloned());
+            },
+
+            CopyInsertOp::Insert(ref elems) => out.extend(elems.iter().cloned())
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the bytes that `ops` encode to `out`, copying from `a`, without holding all of them in
+/// memory at once. This is `apply_copy_insert_into` for rebuilding large files.
+///
+/// If a `Copy` reaches past the end of `a`, this returns an error of kind `InvalidData` wrapping
+/// a `PatchError`, after writing everything before it.
+///
+/// This requires the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{write_copy_insert, LcsTable};
+///
+/// let a = b"one two three".to_vec();
+/// let b = b"one 2 three".to_vec();
+/// let ops = LcsTable::new(&a, &b).to_copy_insert();
+///
+/// let mut out = Vec::new();
+/// write_copy_insert(&a, &ops, &mut out).unwrap();
+/// assert_eq!(out, b);
+/// ```
+#[cfg(feature = "std")]
+pub fn write_copy_insert<W>(a: &[u8], ops: &[CopyInsertOp<u8>], out: &mut W) -> io::Result<()>
+        where W: io::Write {
+    for op in ops {
+        match *op {
+            CopyInsertOp::Copy { src_start, len } => {
+                let run = copied_run(a, src_start, len)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                out.write_all(run)?;
+            },
+
+            CopyInsertOp::Insert(ref elems) => out.write_all(elems)?
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the run of `a` a `Copy` operation refers to, or an error if it's out of range.
+fn copied_run<T>(a: &[T], src_start: usize, len: usize) -> Result<&[T], PatchError> {
+    src_start.checked_add(len)
+        .and_then(|end| a.get(src_start..end))
+        .ok_or(PatchError::OutOfBounds { index: src_start })
+}
+
+#[test]
+fn test_to_copy_insert() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x4d2b_e0c7_9a15_f386, 300) {
+        let table = LcsTable::new(&a, &b);
+        let ops = table.to_copy_insert();
+        assert_eq!(apply_copy_insert(&a, &ops), Ok(b.clone()));
+
+        // Every unchanged element is copied, and runs of insertions are never split up.
+        let copied: usize = ops.iter()
+            .map(|op| match *op {
+                CopyInsertOp::Copy { len, .. } => len,
+                CopyInsertOp::Insert(_) => 0
+            })
+            .sum();
+        assert_eq!(copied, table.length());
+        assert!(ops.windows(2).all(|pair| {
+            !matches!(*pair, [CopyInsertOp::Insert(_), CopyInsertOp::Insert(_)])
+        }));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_write_copy_insert() {
+    let a = b"abc".to_vec();
+    let ops = vec![CopyInsertOp::Copy { src_start: 1, len: 2 }, CopyInsertOp::Insert(vec![b'!'])];
+
+    let mut out = Vec::new();
+    write_copy_insert(&a, &ops, &mut out).unwrap();
+    assert_eq!(out, b"bc!");
+
+    let ops = vec![
+        CopyInsertOp::Insert(vec![b'x']),
+        CopyInsertOp::Copy { src_start: usize::MAX, len: 2 }
+    ];
+    let mut out = Vec::new();
+    let err = write_copy_insert(&a, &ops, &mut out).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(out, b"x");
+}
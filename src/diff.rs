@@ -0,0 +1,475 @@
+//! Post-processing of diffs produced by `LcsTable::diff` and friends.
+
+use std::iter::FromIterator;
+use std::ops::{Deref, Range};
+use std::slice;
+
+use {DiffComponent, DiffStats, LcsTable};
+use prelude::*;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+#[cfg(feature = "std")]
+use std::vec;
+
+/// A diff from `a` to `b`, as returned by `LcsTable::diff`. It dereferences to a slice of
+/// components, so it can be indexed and iterated like the `Vec` it wraps, and it has methods for
+/// the common ways of post-processing a diff.
+///
+/// A diff from elsewhere, such as `myers_diff`, can be collected into a `Diff` to use those
+/// methods too.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{Diff, DiffComponent, DiffStats, LcsTable};
+///
+/// let a: Vec<_> = "axb".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// let table = LcsTable::new(&a, &b);
+/// let diff = table.diff();
+/// assert_eq!(diff[1], DiffComponent::Deletion(&'x'));
+/// assert_eq!(diff.stats(), DiffStats { insertions: 1, deletions: 1, unchanged: 2 });
+///
+/// let myers: Diff<_> = lcs::myers_diff(&a, &b).into_iter().collect();
+/// assert_eq!(diff, myers);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diff<'a, T: 'a> {
+    components: Vec<DiffComponent<&'a T>>
+}
+
+impl<'a, T> Diff<'a, T> {
+    /// Counts the insertions, deletions, and unchanged elements in the diff. See
+    /// `LcsTable::diff_stats`.
+    pub fn stats(&self) -> DiffStats {
+        let mut stats = DiffStats::default();
+
+        for component in &self.components {
+            match *component {
+                DiffComponent::Insertion(_) => stats.insertions += 1,
+                DiffComponent::Unchanged(_, _) => stats.unchanged += 1,
+                DiffComponent::Deletion(_) => stats.deletions += 1
+            }
+        }
+
+        stats
+    }
+
+    /// Unwraps the components of the diff.
+    pub fn into_vec(self) -> Vec<DiffComponent<&'a T>> {
+        self.components
+    }
+}
+
+impl<'a, T> Deref for Diff<'a, T> {
+    type Target = [DiffComponent<&'a T>];
+
+    fn deref(&self) -> &[DiffComponent<&'a T>] {
+        &self.components
+    }
+}
+
+impl<'a, T> IntoIterator for Diff<'a, T> {
+    type Item = DiffComponent<&'a T>;
+    type IntoIter = vec::IntoIter<DiffComponent<&'a T>>;
+
+    fn into_iter(self) -> vec::IntoIter<DiffComponent<&'a T>> {
+        self.components.into_iter()
+    }
+}
+
+impl<'a, 'd, T> IntoIterator for &'d Diff<'a, T> {
+    type Item = &'d DiffComponent<&'a T>;
+    type IntoIter = slice::Iter<'d, DiffComponent<&'a T>>;
+
+    fn into_iter(self) -> slice::Iter<'d, DiffComponent<&'a T>> {
+        self.components.iter()
+    }
+}
+
+impl<'a, T> FromIterator<DiffComponent<&'a T>> for Diff<'a, T> {
+    fn from_iter<I>(iter: I) -> Diff<'a, T> where I: IntoIterator<Item = DiffComponent<&'a T>> {
+        Diff { components: iter.into_iter().collect() }
+    }
+}
+
+impl<'a, T> From<Vec<DiffComponent<&'a T>>> for Diff<'a, T> {
+    fn from(components: Vec<DiffComponent<&'a T>>) -> Diff<'a, T> {
+        Diff { components }
+    }
+}
+
+impl<'a, T> From<Diff<'a, T>> for Vec<DiffComponent<&'a T>> {
+    fn from(diff: Diff<'a, T>) -> Vec<DiffComponent<&'a T>> {
+        diff.components
+    }
+}
+
+impl<'a, T> PartialEq<Vec<DiffComponent<&'a T>>> for Diff<'a, T> where T: PartialEq {
+    fn eq(&self, other: &Vec<DiffComponent<&'a T>>) -> bool {
+        self.components == *other
+    }
+}
+
+impl<'a, T> PartialEq<Diff<'a, T>> for Vec<DiffComponent<&'a T>> where T: PartialEq {
+    fn eq(&self, other: &Diff<'a, T>) -> bool {
+        *self == other.components
+    }
+}
+
+/// A run of consecutive diff components of the same kind. See `coalesce`.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiffComponentRun<T> {
+    Insertion(Vec<T>),
+    Unchanged(Vec<T>, Vec<T>),
+    Deletion(Vec<T>)
+}
+
+/// A diff component, along with the indices in `a` and `b` of the elements it holds. See
+/// `LcsTable::diff_indexed`.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedDiffComponent<T> {
+    pub component: DiffComponent<T>,
+
+    /// The index in `a` and the index in `b` of the component's elements. An insertion has no
+    /// index in `a`, and a deletion has none in `b`.
+    pub indices: (Option<usize>, Option<usize>)
+}
+
+/// Groups consecutive components of the same kind in `diff` into runs. The runs are in the same
+/// order as the components, and contain exactly the same elements.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{coalesce, DiffComponentRun, LcsTable};
+///
+/// let a: Vec<_> = "abxyc".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// let table = LcsTable::new(&a, &b);
+/// assert_eq!(coalesce(table.diff()), vec![
+///     DiffComponentRun::Unchanged(vec![&'a', &'b'], vec![&'a', &'b']),
+///     DiffComponentRun::Deletion(vec![&'x', &'y']),
+///     DiffComponentRun::Unchanged(vec![&'c'], vec![&'c'])
+/// ]);
+/// ```
+pub fn coalesce<T, D>(diff: D) -> Vec<DiffComponentRun<T>>
+        where D: IntoIterator<Item = DiffComponent<T>> {
+    let mut runs = Vec::new();
+
+    for component in diff {
+        match (runs.last_mut(), component) {
+            (Some(&mut DiffComponentRun::Insertion(ref mut run)),
+             DiffComponent::Insertion(elem_b)) => run.push(elem_b),
+
+            (Some(&mut DiffComponentRun::Unchanged(ref mut run_a, ref mut run_b)),
+             DiffComponent::Unchanged(elem_a, elem_b)) => {
+                run_a.push(elem_a);
+                run_b.push(elem_b);
+            },
+
+            (Some(&mut DiffComponentRun::Deletion(ref mut run)),
+             DiffComponent::Deletion(elem_a)) => run.push(elem_a),
+
+            (_, DiffComponent::Insertion(elem_b)) => {
+                runs.push(DiffComponentRun::Insertion(vec![elem_b]));
+            },
+
+            (_, DiffComponent::Unchanged(elem_a, elem_b)) => {
+                runs.push(DiffComponentRun::Unchanged(vec![elem_a], vec![elem_b]));
+            },
+
+            (_, DiffComponent::Deletion(elem_a)) => {
+                runs.push(DiffComponentRun::Deletion(vec![elem_a]));
+            }
+        }
+    }
+
+    runs
+}
+
+/// Turns a diff from `a` to `b` into a diff from `b` to `a`, by swapping insertions and deletions
+/// and the order of the elements in each `Unchanged` component.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{invert, DiffComponent, LcsTable};
+///
+/// let a: Vec<_> = "axb".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// let table = LcsTable::new(&a, &b);
+/// assert_eq!(invert(table.diff()), vec![
+///     DiffComponent::Unchanged(&'a', &'a'),
+///     DiffComponent::Insertion(&'x'),
+///     DiffComponent::Unchanged(&'b', &'b'),
+///     DiffComponent::Deletion(&'c')
+/// ]);
+/// ```
+pub fn invert<T, D>(diff: D) -> Vec<DiffComponent<T>>
+        where D: IntoIterator<Item = DiffComponent<T>> {
+    diff.into_iter()
+        .map(|component| match component {
+            DiffComponent::Insertion(elem_b) => DiffComponent::Deletion(elem_b),
+            DiffComponent::Unchanged(elem_a, elem_b) => DiffComponent::Unchanged(elem_b, elem_a),
+            DiffComponent::Deletion(elem_a) => DiffComponent::Insertion(elem_a)
+        })
+        .collect()
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Computes a diff from `a` to `b` like `diff`, with each component carrying the indices its
+    /// elements came from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, IndexedDiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "ab".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_indexed()[1], IndexedDiffComponent {
+    ///     component: DiffComponent::Deletion(&'x'),
+    ///     indices: (Some(1), None)
+    /// });
+    /// ```
+    pub fn diff_indexed(&self) -> Vec<IndexedDiffComponent<&T>> {
+        let (mut i, mut j) = (0, 0);
+
+        self.diff_iter()
+            .map(|component| {
+                let indices = match component {
+                    DiffComponent::Insertion(_) => (None, Some(j)),
+                    DiffComponent::Unchanged(_, _) => (Some(i), Some(j)),
+                    DiffComponent::Deletion(_) => (Some(i), None)
+                };
+
+                i += indices.0.is_some() as usize;
+                j += indices.1.is_some() as usize;
+                IndexedDiffComponent { component, indices }
+            })
+            .collect()
+    }
+
+    /// Gets the parts of `a` and `b` that the diff changes, as the ranges of indices in `a` that
+    /// are deleted and the ranges of indices in `b` that are inserted. Everything outside of
+    /// those ranges is unchanged.
+    ///
+    /// The ranges of each list are in order, and adjacent changes are merged into one range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abxyc".chars().collect();
+    /// let b: Vec<_> = "abcde".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.changed_ranges(), (vec![2..4], vec![3..5]));
+    /// ```
+    pub fn changed_ranges(&self) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+        let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+        let (mut i, mut j) = (0, 0);
+
+        for component in self.diff_iter() {
+            match component {
+                DiffComponent::Insertion(_) => {
+                    extend_ranges(&mut inserted, j);
+                    j += 1;
+                },
+
+                DiffComponent::Unchanged(_, _) => {
+                    i += 1;
+                    j += 1;
+                },
+
+                DiffComponent::Deletion(_) => {
+                    extend_ranges(&mut deleted, i);
+                    i += 1;
+                }
+            }
+        }
+
+        (deleted, inserted)
+    }
+
+    /// Gets the elements of `a` that aren't part of the longest common subsequence, and the
+    /// elements of `b` that aren't part of it, in order. These are the elements the diff deletes
+    /// and inserts, respectively. If `a` and `b` are identical, both are empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abxyc".chars().collect();
+    /// let b: Vec<_> = "abcde".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.unmatched(), (vec![&'x', &'y'], vec![&'d', &'e']));
+    /// ```
+    pub fn unmatched(&self) -> (Vec<&T>, Vec<&T>) {
+        let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+
+        for component in self.diff_iter() {
+            match component {
+                DiffComponent::Insertion(elem_b) => inserted.push(elem_b),
+                DiffComponent::Unchanged(_, _) => {},
+                DiffComponent::Deletion(elem_a) => deleted.push(elem_a)
+            }
+        }
+
+        (deleted, inserted)
+    }
+}
+
+/// Adds `index` to the last of `ranges` if it comes right after it, or starts a new range with it.
+fn extend_ranges(ranges: &mut Vec<Range<usize>>, index: usize) {
+    match ranges.last_mut() {
+        Some(range) if range.end == index => range.end += 1,
+        _ => ranges.push(index..index + 1)
+    }
+}
+
+#[test]
+fn test_coalesce() {
+    use DiffComponent::*;
+
+    let diff = vec![
+        Deletion('x'), Deletion('y'), Insertion('a'), Unchanged('b', 'b'), Unchanged('c', 'c'),
+        Insertion('d'), Deletion('z')
+    ];
+
+    assert_eq!(coalesce(diff), vec![
+        DiffComponentRun::Deletion(vec!['x', 'y']),
+        DiffComponentRun::Insertion(vec!['a']),
+        DiffComponentRun::Unchanged(vec!['b', 'c'], vec!['b', 'c']),
+        DiffComponentRun::Insertion(vec!['d']),
+        DiffComponentRun::Deletion(vec!['z'])
+    ]);
+
+    assert!(coalesce::<char, _>(vec![]).is_empty());
+}
+
+#[test]
+fn test_invert() {
+    use {apply, random_strings, LcsTable};
+
+    for (a, b) in random_strings(0x94d0_49bb_1331_11eb, 200) {
+        let table = LcsTable::new(&a, &b);
+        let inverted = invert(table.diff());
+
+        assert_eq!(Ok(a.clone()), apply(&b, &inverted));
+        assert_eq!(table.diff(), invert(inverted));
+    }
+}
+
+#[test]
+fn test_diff_indexed() {
+    use random_strings;
+
+    for (a, b) in random_strings(0xbf58_476d_1ce4_e5b9, 200) {
+        let table = LcsTable::new(&a, &b);
+        let indexed = table.diff_indexed();
+
+        for component in &indexed {
+            match (&component.component, component.indices) {
+                (&DiffComponent::Insertion(elem_b), (None, Some(j))) => assert_eq!(elem_b, &b[j]),
+                (&DiffComponent::Unchanged(elem_a, elem_b), (Some(i), Some(j))) => {
+                    assert!(elem_a == &a[i] && elem_b == &b[j]);
+                },
+                (&DiffComponent::Deletion(elem_a), (Some(i), None)) => assert_eq!(elem_a, &a[i]),
+                _ => panic!("unexpected indices {:?}", component)
+            }
+        }
+
+        let components: Vec<_> = indexed.into_iter().map(|indexed| indexed.component).collect();
+        assert_eq!(table.diff(), components);
+    }
+}
+
+#[test]
+fn test_changed_ranges() {
+    use random_strings;
+
+    let empty: Vec<char> = vec![];
+    assert_eq!((vec![], vec![]), LcsTable::new(&empty, &empty).changed_ranges());
+
+    for (a, b) in random_strings(0xd6e8_feb8_6659_fd93, 200) {
+        let table = LcsTable::new(&a, &b);
+        let (deleted, inserted) = table.changed_ranges();
+
+        let stats = table.diff_stats();
+        assert_eq!(stats.deletions, deleted.iter().map(|range| range.len()).sum::<usize>());
+        assert_eq!(stats.insertions, inserted.iter().map(|range| range.len()).sum::<usize>());
+
+        // What's left outside the ranges is the longest common subsequence.
+        let kept = |seq: &[char], ranges: &[Range<usize>]| -> Vec<char> {
+            (0..seq.len())
+                .filter(|&index| !ranges.iter().any(|range| range.contains(&index)))
+                .map(|index| seq[index])
+                .collect()
+        };
+        let lcs: Vec<_> = table.longest_common_subsequence().into_iter()
+            .map(|(elem_a, _)| *elem_a)
+            .collect();
+        assert_eq!(lcs, kept(&a, &deleted));
+        assert_eq!(lcs, kept(&b, &inserted));
+    }
+}
+
+#[test]
+fn test_unmatched() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x2d61_f0b8_5e93_c47a, 100) {
+        let table = LcsTable::new(&a, &b);
+        let (deleted, inserted) = table.unmatched();
+
+        assert_eq!(deleted.len(), a.len() - table.length());
+        assert_eq!(inserted.len(), b.len() - table.length());
+
+        let diff = table.diff().into_vec();
+        let diff_deleted: Vec<_> = diff.iter().filter_map(|component| match *component {
+            DiffComponent::Deletion(elem_a) => Some(elem_a),
+            _ => None
+        }).collect();
+        let diff_inserted: Vec<_> = diff.iter().filter_map(|component| match *component {
+            DiffComponent::Insertion(elem_b) => Some(elem_b),
+            _ => None
+        }).collect();
+        assert_eq!(deleted, diff_deleted);
+        assert_eq!(inserted, diff_inserted);
+    }
+
+    let a: Vec<_> = "abc".chars().collect();
+    assert_eq!(LcsTable::new(&a, &a).unmatched(), (vec![], vec![]));
+}
+
+#[test]
+fn test_diff_wrapper() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x8538_ecb5_bd45_6ea3, 200) {
+        let table = LcsTable::new(&a, &b);
+        let diff = table.diff();
+
+        assert_eq!(table.diff_stats(), diff.stats());
+        assert_eq!(table.diff_hunks(), diff.hunks());
+        assert_eq!(diff.len(), (&diff).into_iter().count());
+
+        let components = table.diff_iter().collect::<Vec<_>>();
+        assert_eq!(diff, components);
+        assert_eq!(Diff::from(components), diff);
+        assert_eq!(table.diff_iter().collect::<Vec<_>>(), diff.into_vec());
+    }
+}
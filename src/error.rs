@@ -0,0 +1,34 @@
+//! The ways constructing an `LcsTable` can fail, for the constructors that report failure instead
+//! of aborting.
+
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+
+/// Why `LcsTable::try_new` couldn't construct a table. Each variant holds the lengths of the two
+/// inputs; the table would have had `len_a + 1` rows and `len_b + 1` columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LcsError {
+    /// The table would have more entries than allowed, or more than fit in a `usize`.
+    TooLarge { len_a: usize, len_b: usize },
+
+    /// There wasn't enough memory for the table.
+    AllocationFailed { len_a: usize, len_b: usize }
+}
+
+impl fmt::Display for LcsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LcsError::TooLarge { len_a, len_b } => {
+                write!(f, "table for inputs of lengths {} and {} is too large", len_a, len_b)
+            },
+
+            LcsError::AllocationFailed { len_a, len_b } => {
+                write!(f, "could not allocate table for inputs of lengths {} and {}", len_a, len_b)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for LcsError {}
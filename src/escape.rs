@@ -0,0 +1,30 @@
+//! Displaying values with some of their characters escaped, shared by the renderers that need it.
+
+use std::fmt::{self, Display, Write};
+
+/// Writes a string to a formatter, escaping whatever the output format needs escaped.
+pub(crate) type EscapeFn = fn(&mut fmt::Formatter, &str) -> fmt::Result;
+
+/// Displays a value with its output passed through `escape`.
+pub(crate) struct Escaped<'t, T: 't + ?Sized> {
+    pub value: &'t T,
+    pub escape: EscapeFn
+}
+
+impl<'t, T> Display for Escaped<'t, T> where T: Display + ?Sized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(Escaper { f, escape: self.escape }, "{}", self.value)
+    }
+}
+
+/// Escapes everything written through it before passing it on.
+struct Escaper<'f, 'g: 'f> {
+    f: &'f mut fmt::Formatter<'g>,
+    escape: EscapeFn
+}
+
+impl<'f, 'g> Write for Escaper<'f, 'g> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        (self.escape)(self.f, s)
+    }
+}
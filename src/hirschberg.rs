@@ -0,0 +1,152 @@
+//! [Hirschberg's algorithm][wiki] for finding a longest common subsequence in linear space.
+//!
+//! `LcsTable` holds onto an *n x m* table, which is too much memory for very large inputs. The
+//! functions in this module recover a longest common subsequence using only O(min(n, m)) space,
+//! at the cost of roughly doubling the running time.
+//!
+//! [wiki]: https://en.wikipedia.org/wiki/Hirschberg%27s_algorithm
+
+use std::cmp;
+
+use prelude::*;
+
+/// Gets a longest common subsequence between `a` and `b` without constructing an `LcsTable`.
+/// Returned elements are in the same form as `LcsTable::longest_common_subsequence`.
+///
+/// When there are several longest common subsequences, this may pick a different one than
+/// `LcsTable` would, but it will always be of the same length.
+///
+/// Example:
+///
+/// ```
+/// use lcs::hirschberg;
+///
+/// let a: Vec<_> = "a--b---c".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// let lcs = hirschberg::longest_common_subsequence(&a, &b);
+/// assert_eq!(vec![(&'a', &'a'), (&'b', &'b'), (&'c', &'c')], lcs);
+/// ```
+pub fn longest_common_subsequence<'a, T>(a: &'a [T], b: &'a [T]) -> Vec<(&'a T, &'a T)>
+        where T: Eq {
+    let mut lcs = Vec::new();
+
+    // Rows are as long as the second sequence, so make sure that one is the shorter of the two.
+    if a.len() < b.len() {
+        find_lcs(b, a, &mut lcs);
+        lcs.into_iter().map(|(elem_b, elem_a)| (elem_a, elem_b)).collect()
+    } else {
+        find_lcs(a, b, &mut lcs);
+        lcs
+    }
+}
+
+fn find_lcs<'a, T>(a: &'a [T], b: &'a [T], lcs: &mut Vec<(&'a T, &'a T)>) where T: Eq {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+
+    if a.len() == 1 {
+        if let Some(elem_b) = b.iter().find(|elem_b| **elem_b == a[0]) {
+            lcs.push((&a[0], elem_b));
+        }
+
+        return;
+    }
+
+    let mid = a.len() / 2;
+
+    // The rows are dropped at the end of this block, so that they aren't kept around through the
+    // recursive calls below.
+    let split = {
+        let forward = lcs_row(a[..mid].iter(), b.iter());
+        let backward = lcs_row(a[mid..].iter().rev(), b.iter().rev());
+
+        // `forward[k]` is the LCS length of `a[..mid]` and `b[..k]`, and `backward[b.len() - k]`
+        // is the LCS length of `a[mid..]` and `b[k..]`. The best place to split `b` maximizes
+        // their sum.
+        (0..b.len() + 1)
+            .max_by_key(|&k| (forward[k] + backward[b.len() - k], cmp::Reverse(k)))
+            .unwrap()
+    };
+
+    find_lcs(&a[..mid], &b[..split], lcs);
+    find_lcs(&a[mid..], &b[split..], lcs);
+}
+
+/// Computes the last row of the LCS length table for `a` and `b`, keeping only one row in memory.
+pub(crate) fn lcs_row<'a, T, A, B>(a: A, b: B) -> Vec<usize>
+        where T: 'a + Eq, A: Iterator<Item = &'a T>, B: Iterator<Item = &'a T> + Clone {
+    let mut row = vec![0; b.clone().count() + 1];
+
+    for elem_a in a {
+        let mut diagonal = 0;
+
+        for (j, elem_b) in b.clone().enumerate() {
+            let above = row[j + 1];
+
+            row[j + 1] = if elem_a == elem_b {
+                diagonal + 1
+            } else {
+                cmp::max(row[j], above)
+            };
+
+            diagonal = above;
+        }
+    }
+
+    row
+}
+
+#[test]
+fn test_hirschberg_empty() {
+    let a: Vec<char> = vec![];
+    let b: Vec<_> = "abc".chars().collect();
+
+    assert!(longest_common_subsequence(&a, &b).is_empty());
+    assert!(longest_common_subsequence(&b, &a).is_empty());
+    assert!(longest_common_subsequence(&a, &a).is_empty());
+}
+
+#[test]
+fn test_hirschberg_prefix() {
+    let a: Vec<_> = "abc".chars().collect();
+    let b: Vec<_> = "abcdef".chars().collect();
+
+    let expected = vec![(&'a', &'a'), (&'b', &'b'), (&'c', &'c')];
+    assert_eq!(expected, longest_common_subsequence(&a, &b));
+    assert_eq!(expected, longest_common_subsequence(&b, &a));
+}
+
+#[test]
+fn test_hirschberg_all_equal() {
+    let a = vec!['x'; 7];
+    let b = vec!['x'; 4];
+
+    assert_eq!(vec![(&'x', &'x'); 4], longest_common_subsequence(&a, &b));
+    assert_eq!(vec![(&'x', &'x'); 4], longest_common_subsequence(&b, &a));
+    assert_eq!(vec![(&'x', &'x'); 7], longest_common_subsequence(&a, &a));
+}
+
+#[test]
+fn test_hirschberg_matches_table() {
+    use std::ptr;
+    use {random_strings, LcsTable};
+
+    for (a, b) in random_strings(0x2545_f491_4f6c_dd1d, 500) {
+        let lcs = longest_common_subsequence(&a, &b);
+        let expected = LcsTable::new(&a, &b).longest_common_subsequence().len();
+        assert_eq!(expected, lcs.len());
+
+        // Every returned pair must point into `a` and `b` respectively, in increasing order.
+        let mut rest_a = &a[..];
+        let mut rest_b = &b[..];
+        for (elem_a, elem_b) in lcs {
+            assert_eq!(elem_a, elem_b);
+            let i = rest_a.iter().position(|x| ptr::eq(x, elem_a)).unwrap();
+            let j = rest_b.iter().position(|x| ptr::eq(x, elem_b)).unwrap();
+            rest_a = &rest_a[i + 1..];
+            rest_b = &rest_b[j + 1..];
+        }
+    }
+}
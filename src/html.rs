@@ -0,0 +1,153 @@
+//! Rendering diffs as HTML.
+
+use std::fmt::{self, Display, Write};
+
+use escape::Escaped;
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// How `LcsTable::to_html` marks up insertions and deletions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlConfig {
+    /// The tag wrapped around each run of inserted elements, `ins` by default.
+    pub insertion_tag: String,
+
+    /// The tag wrapped around each run of deleted elements, `del` by default.
+    pub deletion_tag: String,
+
+    /// The `class` attribute to give insertion tags, if any.
+    pub insertion_class: Option<String>,
+
+    /// The `class` attribute to give deletion tags, if any.
+    pub deletion_class: Option<String>
+}
+
+impl Default for HtmlConfig {
+    fn default() -> HtmlConfig {
+        HtmlConfig {
+            insertion_tag: String::from("ins"),
+            deletion_tag: String::from("del"),
+            insertion_class: None,
+            deletion_class: None
+        }
+    }
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: Display, E: Fn(&T, &T) -> bool {
+    /// Renders the diff from `a` to `b` as HTML. Elements are written one after the other, with
+    /// each run of insertions or deletions wrapped in a tag as described by `config`. The text of
+    /// each element is escaped, so elements containing `<`, `>`, `&`, or quotes are safe to
+    /// render.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{HtmlConfig, LcsTable};
+    ///
+    /// let a: Vec<_> = "a<b".chars().collect();
+    /// let b: Vec<_> = "a>b".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.to_html(&HtmlConfig::default()), "a<ins>&gt;</ins><del>&lt;</del>b");
+    ///
+    /// let config = HtmlConfig {
+    ///     insertion_tag: "span".to_string(),
+    ///     insertion_class: Some("added".to_string()),
+    ///     ..HtmlConfig::default()
+    /// };
+    /// assert_eq!(table.to_html(&config), "a<span class=\"added\">&gt;</span><del>&lt;</del>b");
+    /// ```
+    pub fn to_html(&self, config: &HtmlConfig) -> String {
+        let mut html = String::new();
+        let mut open: Option<&str> = None;
+
+        for component in self.diff_iter() {
+            let (tag, class, elem) = match component {
+                DiffComponent::Insertion(elem_b) => {
+                    (Some(&config.insertion_tag), &config.insertion_class, elem_b)
+                },
+                DiffComponent::Unchanged(_, elem_b) => (None, &None, elem_b),
+                DiffComponent::Deletion(elem_a) => {
+                    (Some(&config.deletion_tag), &config.deletion_class, elem_a)
+                }
+            };
+
+            let tag = tag.map(|tag| &tag[..]);
+            if tag != open {
+                if let Some(open) = open {
+                    write!(html, "</{}>", open).unwrap();
+                }
+
+                if let Some(tag) = tag {
+                    write!(html, "<{}", tag).unwrap();
+                    if let Some(ref class) = *class {
+                        write!(html, " class=\"{}\"", escaped(class)).unwrap();
+                    }
+                    html.push('>');
+                }
+
+                open = tag;
+            }
+
+            write!(html, "{}", escaped(elem)).unwrap();
+        }
+
+        if let Some(open) = open {
+            write!(html, "</{}>", open).unwrap();
+        }
+
+        html
+    }
+}
+
+/// Displays a value with the characters that are special in HTML escaped.
+fn escaped<'t, T: ?Sized>(value: &'t T) -> Escaped<'t, T> {
+    Escaped { value, escape: escape_html }
+}
+
+fn escape_html(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let mut rest = s;
+
+    while let Some(index) = rest.find(['&', '<', '>', '"', '\'']) {
+        f.write_str(&rest[..index])?;
+        f.write_str(match rest.as_bytes()[index] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            _ => "&#39;"
+        })?;
+
+        rest = &rest[index + 1..];
+    }
+
+    f.write_str(rest)
+}
+
+#[test]
+fn test_to_html_escapes() {
+    let a = vec!["<p>", "fish & chips", "</p>"];
+    let b = vec!["<p>", "\"fish\" & 'chips'", "</p>"];
+
+    let config = HtmlConfig {
+        deletion_class: Some(String::from("a\"b")),
+        ..HtmlConfig::default()
+    };
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.to_html(&config), concat!(
+        "&lt;p&gt;",
+        "<ins>&quot;fish&quot; &amp; &#39;chips&#39;</ins>",
+        "<del class=\"a&quot;b\">fish &amp; chips</del>",
+        "&lt;/p&gt;"
+    ));
+}
+
+#[test]
+fn test_to_html_groups_runs() {
+    let a: Vec<_> = "abcxyz".chars().collect();
+    let b: Vec<_> = "a12cz".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.to_html(&HtmlConfig::default()), "a<ins>12</ins><del>b</del>c<del>xy</del>z");
+}
@@ -0,0 +1,256 @@
+//! Grouping a diff into contiguous regions of change.
+
+use {lcs_length, Diff, DiffComponent, LcsTable};
+use prelude::*;
+use text::str_diff;
+
+/// A contiguous region of change in a diff: some elements deleted from `a`, and some elements
+/// inserted from `b`, with no unchanged elements in between.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Hunk<T> {
+    /// The index in `a` of the first deleted element, or where insertions happen if there are no
+    /// deletions.
+    pub a_start: usize,
+
+    /// The index in `b` of the first inserted element, or where deletions happen if there are no
+    /// insertions.
+    pub b_start: usize,
+
+    /// The elements deleted from `a`, in order.
+    pub deletions: Vec<T>,
+
+    /// The elements inserted from `b`, in order.
+    pub insertions: Vec<T>
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Groups the diff from `a` to `b` into hunks of consecutive insertions and deletions.
+    /// Unchanged elements are skipped; they are implied by the gaps between hunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{Hunk, LcsTable};
+    ///
+    /// let a: Vec<_> = "axyb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_hunks(), vec![
+    ///     Hunk { a_start: 1, b_start: 1, deletions: vec![&'x', &'y'], insertions: vec![] },
+    ///     Hunk { a_start: 4, b_start: 2, deletions: vec![], insertions: vec![&'c'] }
+    /// ]);
+    /// ```
+    pub fn diff_hunks(&self) -> Vec<Hunk<&T>> {
+        self.diff().hunks()
+    }
+}
+
+impl<'a, T> Diff<'a, T> {
+    /// Groups the diff into hunks of consecutive insertions and deletions. See
+    /// `LcsTable::diff_hunks`.
+    pub fn hunks(&self) -> Vec<Hunk<&'a T>> {
+        let mut hunks = Vec::new();
+        let mut current = None;
+        let (mut i, mut j) = (0, 0);
+
+        for component in self {
+            match *component {
+                DiffComponent::Insertion(elem_b) => {
+                    current.get_or_insert_with(|| empty_hunk(i, j)).insertions.push(elem_b);
+                    j += 1;
+                },
+
+                DiffComponent::Unchanged(_, _) => {
+                    hunks.extend(current.take());
+                    i += 1;
+                    j += 1;
+                },
+
+                DiffComponent::Deletion(elem_a) => {
+                    current.get_or_insert_with(|| empty_hunk(i, j)).deletions.push(elem_a);
+                    i += 1;
+                }
+            }
+        }
+
+        hunks.extend(current);
+        hunks
+    }
+}
+
+impl<T> Hunk<T> where T: AsRef<str> {
+    /// Gets how similar the deleted and inserted text of a hunk is, from 0.0 to 1.0, such as for
+    /// telling a line that was slightly reworded apart from one that was replaced outright. A hunk
+    /// that only deletes or only inserts has a similarity of 0.0.
+    ///
+    /// A hunk never has elements in common between its deletions and insertions, or they would
+    /// have been unchanged. So this compares the text of the elements character by character,
+    /// with the same measure as `LcsTable::ratio`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec!["fn main() {", "    println!(\"hi\");", "}"];
+    /// let b = vec!["fn main() {", "    println!(\"hello\");", "}"];
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let similarity = table.diff_hunks()[0].similarity();
+    /// assert!(similarity > 0.8 && similarity < 1.0);
+    /// ```
+    pub fn similarity(&self) -> f64 {
+        let chars = |elems: &[T]| -> Vec<char> {
+            elems.iter().flat_map(|elem| elem.as_ref().chars()).collect()
+        };
+
+        let (deleted, inserted) = (chars(&self.deletions), chars(&self.insertions));
+        if deleted.is_empty() || inserted.is_empty() {
+            return 0.0;
+        }
+
+        2.0 * lcs_length(&deleted, &inserted) as f64 / (deleted.len() + inserted.len()) as f64
+    }
+}
+
+/// A hunk of a diff, along with a character-by-character diff of its text if its deletions and
+/// insertions are similar enough. See `LcsTable::diff_refined`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RefinedHunk<T> {
+    /// The hunk itself.
+    pub hunk: Hunk<T>,
+
+    /// The diff from the deleted text to the inserted text, with the elements of each joined by
+    /// newlines, or `None` if they weren't similar enough to be worth refining.
+    pub refinement: Option<Vec<DiffComponent<char>>>
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: AsRef<str>, E: Fn(&T, &T) -> bool {
+    /// Groups the diff from `a` to `b` into hunks like `diff_hunks`, and refines the ones whose
+    /// deleted and inserted text have a `Hunk::similarity` of at least `threshold` with a diff of
+    /// their characters. This is the two-level diff code review tools show, where a slightly
+    /// edited line is highlighted where it changed, rather than as a whole.
+    ///
+    /// A `threshold` around 0.5 refines lines that were edited while leaving ones that were
+    /// rewritten alone, where highlighting the few characters they happen to share would be noise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a = vec!["let x = 1;", "let y = 2;"];
+    /// let b = vec!["let x = 10;", "return;"];
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let refined = table.diff_refined(0.5);
+    /// assert_eq!(refined.len(), 1);
+    ///
+    /// let refinement = refined[0].refinement.as_ref().unwrap();
+    /// assert!(refinement.contains(&DiffComponent::Insertion('0')));
+    /// assert_eq!(LcsTable::new(&a, &b).diff_refined(0.9)[0].refinement, None);
+    /// ```
+    pub fn diff_refined(&self, threshold: f64) -> Vec<RefinedHunk<&T>> {
+        self.diff_hunks().into_iter()
+            .map(|hunk| {
+                let join = |elems: &[&T]| -> String {
+                    let lines: Vec<_> = elems.iter().map(|elem| elem.as_ref()).collect();
+                    lines.join("\n")
+                };
+
+                let refinement = if hunk.similarity() >= threshold {
+                    Some(str_diff(&join(&hunk.deletions), &join(&hunk.insertions)))
+                } else {
+                    None
+                };
+
+                RefinedHunk { hunk, refinement }
+            })
+            .collect()
+    }
+}
+
+/// Computes the diff between the deleted and inserted elements of a hunk, such as the characters
+/// of an edited line. This is the same as an ordinary diff between them; it's the second level of
+/// a two-level diff like `LcsTable::diff_refined`, for elements that aren't text.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{refine_hunk, DiffComponent};
+///
+/// let deleted = [1, 2, 3];
+/// let inserted = [1, 3];
+///
+/// assert_eq!(refine_hunk(&deleted, &inserted), vec![
+///     DiffComponent::Unchanged(&1, &1),
+///     DiffComponent::Deletion(&2),
+///     DiffComponent::Unchanged(&3, &3)
+/// ]);
+/// ```
+pub fn refine_hunk<'a, T>(deleted: &'a [T], inserted: &'a [T]) -> Vec<DiffComponent<&'a T>>
+        where T: Eq {
+    LcsTable::new(deleted, inserted).diff().into_vec()
+}
+
+fn empty_hunk<T>(a_start: usize, b_start: usize) -> Hunk<T> {
+    Hunk { a_start, b_start, deletions: vec![], insertions: vec![] }
+}
+
+#[test]
+fn test_diff_hunks() {
+    let a: Vec<_> = "xaybzc".chars().collect();
+    let b: Vec<_> = "abwc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let hunks = table.diff_hunks();
+    assert_eq!(hunks.len(), 3);
+
+    assert_eq!(hunks[0], Hunk { a_start: 0, b_start: 0, deletions: vec![&'x'], insertions: vec![] });
+    assert_eq!(hunks[1], Hunk { a_start: 2, b_start: 1, deletions: vec![&'y'], insertions: vec![] });
+    assert_eq!((hunks[2].a_start, hunks[2].b_start), (4, 2));
+    assert_eq!(hunks[2].deletions, vec![&'z']);
+    assert_eq!(hunks[2].insertions, vec![&'w']);
+}
+
+#[test]
+fn test_diff_hunks_identical() {
+    let a: Vec<_> = "abc".chars().collect();
+    let empty: Vec<char> = vec![];
+
+    assert!(LcsTable::new(&a, &a).diff_hunks().is_empty());
+    assert!(LcsTable::new(&empty, &empty).diff_hunks().is_empty());
+}
+
+#[test]
+fn test_hunk_similarity() {
+    let a = vec!["same", "old line", "deleted", "same"];
+    let b = vec!["same", "odd line", "same", "inserted"];
+
+    let table = LcsTable::new(&a, &b);
+    let similarities: Vec<_> = table.diff_hunks().iter().map(|hunk| hunk.similarity()).collect();
+
+    // "old line" and "deleted" against "odd line" have "od line" in common.
+    assert_eq!(similarities, vec![2.0 * 7.0 / 23.0, 0.0]);
+}
+
+#[test]
+fn test_diff_refined() {
+    let a = vec!["same", "colour", "shade", "same", "old"];
+    let b = vec!["same", "color", "shades", "same", "new"];
+
+    let table = LcsTable::new(&a, &b);
+    let refined = table.diff_refined(0.5);
+    assert_eq!(refined.len(), 2);
+
+    let refinement = refined[0].refinement.as_ref().unwrap();
+    let changes: Vec<_> = refinement.iter()
+        .filter(|component| !matches!(**component, DiffComponent::Unchanged(_, _)))
+        .collect();
+    assert_eq!(changes, vec![&DiffComponent::Deletion('u'), &DiffComponent::Insertion('s')]);
+
+    // "old" and "new" have nothing in common.
+    assert_eq!(refined[1].refinement, None);
+    assert_eq!(refined[1].hunk, table.diff_hunks()[1]);
+}
@@ -0,0 +1,74 @@
+//! Updating an `LcsTable` when one of its sequences changes, without starting over.
+
+use std::cmp;
+
+use {Cow, LcsTable};
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: PartialEq, E: Fn(&T, &T) -> bool {
+    /// Replaces `b` with `new_b`, updating the table to match. The result is the same as building
+    /// a new table for `a` and `new_b`.
+    ///
+    /// Each column of the table only depends on the elements of `b` before it, so the columns for
+    /// the prefix that `b` and `new_b` have in common are kept as they are, and only the rest are
+    /// recomputed. This makes an edit near the end of `b`, like typing in an editor, much cheaper
+    /// than building a new table. Elements of `b` and `new_b` are compared with `==`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "a quick fox".chars().collect();
+    /// let b: Vec<_> = "a quick".chars().collect();
+    /// let new_b: Vec<_> = "a quick brown fox".chars().collect();
+    ///
+    /// let mut table = LcsTable::new(&a, &b);
+    /// assert_eq!(7, table.length());
+    ///
+    /// table.update_b(&new_b);
+    /// assert_eq!(11, table.length());
+    /// ```
+    pub fn update_b(&mut self, new_b: &'a [T]) {
+        let prefix = self.b.iter().zip(new_b).take_while(|&(old, new)| old == new).count();
+        let (old_width, width) = (self.b.len() + 1, new_b.len() + 1);
+        let mut lengths = vec![0; (self.a.len() + 1) * width];
+
+        for (row, old_row) in lengths.chunks_mut(width).zip(self.lengths.chunks(old_width)) {
+            row[..prefix + 1].copy_from_slice(&old_row[..prefix + 1]);
+        }
+
+        for (i, elem_a) in self.a.iter().enumerate() {
+            let (above, current) = lengths[i * width..(i + 2) * width].split_at_mut(width);
+
+            for (j, elem_b) in new_b.iter().enumerate().skip(prefix) {
+                current[j + 1] = if (self.eq)(elem_a, elem_b) {
+                    1 + above[j]
+                } else {
+                    cmp::max(current[j], above[j + 1])
+                }
+            }
+        }
+
+        self.lengths = Cow::Owned(lengths);
+        self.b = new_b;
+    }
+}
+
+#[test]
+fn test_update_b() {
+    use random_strings;
+    use prelude::*;
+
+    let mut strings = random_strings(0x71c4_9a0e_d3b2_5f86, 100).into_iter();
+    while let (Some((a, b)), Some((_, suffix))) = (strings.next(), strings.next()) {
+        let half = b.len() / 2;
+        let new_b: Vec<_> = b[..half].iter().chain(&suffix).cloned().collect();
+
+        let mut table = LcsTable::new(&a, &b);
+        table.update_b(&new_b);
+        assert_eq!(table, LcsTable::new(&a, &new_b));
+
+        table.update_b(&a);
+        assert_eq!(table, LcsTable::new(&a, &a));
+    }
+}
@@ -0,0 +1,110 @@
+//! Rendering diffs as JSON, for consumers that don't speak Rust.
+
+use std::fmt::{self, Display, Write};
+
+use escape::Escaped;
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: Display, E: Fn(&T, &T) -> bool {
+    /// Renders the diff from `a` to `b` as a JSON array, with one object per component of the
+    /// diff, in order. Each object has exactly two keys, in this order:
+    ///
+    /// * `op`, which is one of `"insertion"`, `"unchanged"`, or `"deletion"`.
+    /// * `value`, which is the element as a string, formatted with `Display`. For unchanged
+    ///   elements, this is the element from `a`.
+    ///
+    /// The output has no whitespace outside of strings. This format is stable, and won't change
+    /// between versions other than breaking ones. It doesn't need the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec!["a", "b"];
+    /// let b = vec!["a", "c"];
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.to_json(), concat!(
+    ///     r#"[{"op":"unchanged","value":"a"},"#,
+    ///     r#"{"op":"insertion","value":"c"},"#,
+    ///     r#"{"op":"deletion","value":"b"}]"#
+    /// ));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+
+        for (k, component) in self.diff_iter().enumerate() {
+            let (op, elem) = match component {
+                DiffComponent::Insertion(elem_b) => ("insertion", elem_b),
+                DiffComponent::Unchanged(elem_a, _) => ("unchanged", elem_a),
+                DiffComponent::Deletion(elem_a) => ("deletion", elem_a)
+            };
+
+            if k > 0 {
+                json.push(',');
+            }
+
+            write!(json, "{{\"op\":\"{}\",\"value\":\"{}\"}}", op, escaped(elem)).unwrap();
+        }
+
+        json.push(']');
+        json
+    }
+}
+
+/// Displays a value with the characters that aren't allowed in JSON strings escaped.
+fn escaped<'t, T: ?Sized>(value: &'t T) -> Escaped<'t, T> {
+    Escaped { value, escape: escape_json }
+}
+
+fn escape_json(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let mut rest = s;
+
+    while let Some(index) = rest.find(|c: char| c == '"' || c == '\\' || c < ' ') {
+        f.write_str(&rest[..index])?;
+        match rest.as_bytes()[index] {
+            b'"' => f.write_str("\\\"")?,
+            b'\\' => f.write_str("\\\\")?,
+            b'\n' => f.write_str("\\n")?,
+            b'\r' => f.write_str("\\r")?,
+            b'\t' => f.write_str("\\t")?,
+            byte => write!(f, "\\u{:04x}", byte)?
+        }
+
+        rest = &rest[index + 1..];
+    }
+
+    f.write_str(rest)
+}
+
+#[test]
+fn test_to_json() {
+    let a = vec!["keep", "say \"hi\"", "tab\there"];
+    let b = vec!["keep", "back\\slash\n", "bell\u{7}"];
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.to_json(), concat!(
+        r#"[{"op":"unchanged","value":"keep"},"#,
+        r#"{"op":"insertion","value":"back\\slash\n"},"#,
+        r#"{"op":"insertion","value":"bell\u0007"},"#,
+        r#"{"op":"deletion","value":"say \"hi\""},"#,
+        r#"{"op":"deletion","value":"tab\there"}]"#
+    ));
+
+    let empty: Vec<&str> = vec![];
+    assert_eq!(LcsTable::new(&empty, &empty).to_json(), "[]");
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_to_json_parses() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x5be0_8d17_c6a3_e249, 50) {
+        let table = LcsTable::new(&a, &b);
+        let parsed: serde_json::Value = serde_json::from_str(&table.to_json()).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), table.diff().len());
+    }
+}
@@ -2,48 +2,567 @@
 //! subsequences table, you can also calculate diffs (see `LcsTable::diff`).
 //!
 //! Usage of this crate is centered around `LcsTable`, so most interesting documentation can be
-//! found there.
+//! found there. For inputs too large for an `LcsTable`, see the `hirschberg` module, `myers_diff`,
+//! `TrimmedLcsTable`, and `lcs_length`.
 //!
 //! [wiki]: https://en.wikipedia.org/wiki/Longest_common_subsequence_problem
+//!
+//! The crate depends on `std` by default. Disabling the default `std` feature makes it `no_std`,
+//! needing only `alloc`. The optional `rayon` feature adds `LcsTable::new_parallel`, and the
+//! optional `unicode` feature adds `text::grapheme_diff`. The optional `similar` feature adds
+//! conversions between `ChangeTag` and the [similar][similar] crate's tags.
+//!
+//! [similar]: https://crates.io/crates/similar
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+extern crate core as std;
 
-use std::cmp;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "similar")]
+extern crate similar;
+#[cfg(feature = "unicode")]
+extern crate unicode_segmentation;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
+use std::{cmp, fmt, iter, mem};
+#[cfg(feature = "std")]
 use std::hash::Hash;
+#[cfg(feature = "std")]
 use std::collections::HashSet;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeSet;
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use prelude::*;
+
+/// The parts of the standard prelude that aren't in `core`, so that the rest of the crate can use
+/// them the same way with or without the `std` feature.
+mod prelude {
+    #[cfg(not(feature = "std"))]
+    pub use alloc::string::String;
+    #[cfg(not(feature = "std"))]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::string::String;
+    #[cfg(feature = "std")]
+    pub use std::vec::Vec;
+}
 
-#[derive(Debug)]
-pub struct LcsTable<'a, T: 'a> {
-    lengths: Vec<Vec<i64>>,
+pub mod hirschberg;
+pub mod text;
+pub mod util;
+mod balanced;
+mod banded;
+mod barriers;
+mod classify;
+mod context;
+mod contiguous;
+mod delta;
+mod diff;
+mod error;
+mod escape;
+mod html;
+mod hunk;
+mod incremental;
+mod json;
+mod merge;
+mod metrics;
+mod moves;
+mod multi;
+mod myers;
+mod owned;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod patch;
+mod substring;
+mod summary;
+mod tag;
+mod supersequence;
+mod trimmed;
+mod unified;
+mod weighted;
+mod workspace;
+
+pub use banded::BandedLcsTable;
+pub use classify::ChangeClass;
+pub use context::ContextBlock;
+pub use delta::{apply_copy_insert, apply_copy_insert_into, CopyInsertOp};
+#[cfg(feature = "std")]
+pub use delta::write_copy_insert;
+pub use diff::{coalesce, invert, Diff, DiffComponentRun, IndexedDiffComponent};
+pub use error::LcsError;
+pub use html::HtmlConfig;
+pub use hunk::{refine_hunk, Hunk, RefinedHunk};
+pub use merge::{merge3, MergeChunk};
+pub use metrics::{best_match, bounded_edit_distance, lcs_length};
+#[cfg(feature = "std")]
+pub use metrics::lcs_length_bitparallel;
+pub use moves::MoveAwareComponent;
+pub use multi::lcs3;
+pub use myers::myers_diff;
+pub use owned::OwnedLcsTable;
+pub use patch::{apply, EditOp, PatchError};
+pub use substring::SubstringIndex;
+pub use summary::LcsSummary;
+pub use tag::ChangeTag;
+pub use supersequence::shortest_common_supersequence;
+pub use trimmed::TrimmedLcsTable;
+pub use weighted::WeightedLcsTable;
+pub use workspace::LcsWorkspace;
+
+/// The type parameter `E` is how elements are compared. Tables made with `LcsTable::new` use
+/// `PartialEq`, and ones made with `LcsTable::by_key` compare by key instead.
+pub struct LcsTable<'a, T: 'a, E = fn(&T, &T) -> bool> {
+    // The `(a.len() + 1) x (b.len() + 1)` table, one row after another, so that the entry for
+    // `a[..i]` and `b[..j]` is at `i * (b.len() + 1) + j`. It's only borrowed in tables handed out
+    // by an `OwnedLcsTable`.
+    lengths: Cow<'a, [usize]>,
 
     a: &'a [T],
-    b: &'a [T]
+    b: &'a [T],
+    eq: E
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DiffComponent<T> {
     Insertion(T),
     Unchanged(T, T),
     Deletion(T)
 }
 
+/// Which way `LcsTable::diff_with_bias` goes when an insertion and a deletion would both lead to
+/// equally small diffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBias {
+    /// Take the deletion, as `LcsTable::diff` does.
+    PreferDeletion,
+
+    /// Take the insertion.
+    PreferInsertion
+}
+
+impl Default for TieBias {
+    fn default() -> TieBias {
+        TieBias::PreferDeletion
+    }
+}
+
+/// How many components of each kind a diff has. See `LcsTable::diff_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub insertions: usize,
+    pub deletions: usize,
+    pub unchanged: usize
+}
+
 /// Finding longest common subsequences ("LCS") between two sequences requires constructing a *n x
 /// m* table (where the two sequences are of lengths *n* and *m*). This is expensive to construct
 /// and there's a lot of stuff you can calculate using it, so `LcsTable` holds onto this data.
-impl<'a, T> LcsTable<'a, T> where T: Eq {
+impl<'a, T> LcsTable<'a, T> {
     /// Constructs a LcsTable for matching between two sequences `a` and `b`.
-    pub fn new(a: &'a [T], b: &'a [T]) -> LcsTable<'a, T> {
-        let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+    pub fn new(a: &'a [T], b: &'a [T]) -> LcsTable<'a, T> where T: Eq {
+        let lengths = fill(a.len(), b.len(), |i, j| a[i] == b[j]);
+        LcsTable { lengths: Cow::Owned(lengths), a, b, eq: PartialEq::eq }
+    }
 
-        for i in 0..a.len() {
-            for j in 0..b.len() {
-                lengths[i + 1][j + 1] = if a[i] == b[j] {
-                    1 + lengths[i][j]
-                } else {
-                    cmp::max(lengths[i + 1][j], lengths[i][j + 1])
-                }
+    /// Constructs a LcsTable for matching between two empty sequences, for use as a placeholder
+    /// until there are real inputs. This is also what `LcsTable::default` gives.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let table: LcsTable<char> = LcsTable::empty();
+    /// assert_eq!(0, table.length());
+    /// assert!(table.diff().is_empty());
+    /// ```
+    pub fn empty() -> LcsTable<'a, T> where T: Eq {
+        LcsTable::new(&[], &[])
+    }
+
+    /// Constructs a LcsTable for matching between two sequences `a` and `b`, like `new`, but
+    /// returns an error instead of aborting the process if there isn't enough memory for the
+    /// table. This is the same as `try_new_with_limit` with no limit.
+    ///
+    /// Whether the allocation fails up front depends on the operating system: with memory
+    /// overcommit, a table that's too large may still be allocated, and only fail once it's filled.
+    /// `try_new_with_limit` gives a dependable bound.
+    pub fn try_new(a: &'a [T], b: &'a [T]) -> Result<LcsTable<'a, T>, LcsError> where T: Eq {
+        LcsTable::try_new_with_limit(a, b, usize::MAX)
+    }
+
+    /// Constructs a LcsTable for matching between two sequences `a` and `b`, like `new`, but
+    /// returns an error if the table would have more than `max_entries` entries, or if there isn't
+    /// enough memory for it. The table has `(a.len() + 1) * (b.len() + 1)` entries, each a
+    /// `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{LcsError, LcsTable};
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// assert_eq!(2, LcsTable::try_new_with_limit(&a, &b, 24).unwrap().length());
+    /// assert_eq!(LcsTable::try_new_with_limit(&a, &b, 23),
+    ///            Err(LcsError::TooLarge { len_a: 3, len_b: 5 }));
+    /// ```
+    pub fn try_new_with_limit(a: &'a [T], b: &'a [T], max_entries: usize)
+            -> Result<LcsTable<'a, T>, LcsError> where T: Eq {
+        let (len_a, len_b) = (a.len(), b.len());
+
+        let entries = len_a.checked_add(1)
+            .and_then(|rows| len_b.checked_add(1).and_then(|columns| rows.checked_mul(columns)))
+            .filter(|&entries| entries <= max_entries)
+            .ok_or(LcsError::TooLarge { len_a, len_b })?;
+
+        let mut lengths = Vec::new();
+        lengths.try_reserve_exact(entries)
+            .map_err(|_| LcsError::AllocationFailed { len_a, len_b })?;
+
+        fill_into(&mut lengths, len_a, len_b, |i, j| a[i] == b[j]);
+        Ok(LcsTable { lengths: Cow::Owned(lengths), a, b, eq: PartialEq::eq })
+    }
+
+    /// Constructs a LcsTable for matching between any two containers that can be viewed as
+    /// slices, such as `Vec<T>`, `Box<[T]>`, or a type of your own implementing `AsRef<[T]>`. The
+    /// table borrows from `a` and `b`, just like with `new`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Box<[char]> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::from_sequences(&a, &b);
+    /// assert_eq!(2, table.length());
+    /// ```
+    pub fn from_sequences<A, B>(a: &'a A, b: &'a B) -> LcsTable<'a, T>
+            where T: Eq, A: AsRef<[T]> + ?Sized, B: AsRef<[T]> + ?Sized {
+        LcsTable::new(a.as_ref(), b.as_ref())
+    }
+
+    /// Constructs a LcsTable for matching between two sequences `a` and `b`, where two elements
+    /// match if they have the same `key`. Everything computed from the table still refers to the
+    /// whole elements, so `Unchanged` components can pair up elements that differ in other ways.
+    ///
+    /// `key` is called once per element while building the table, and again whenever elements are
+    /// compared afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a = vec![(1, "one"), (2, "two")];
+    /// let b = vec![(2, "deux"), (3, "trois")];
+    ///
+    /// let table = LcsTable::by_key(&a, &b, |&(id, _)| id);
+    /// assert_eq!(table.diff(), vec![
+    ///     DiffComponent::Deletion(&(1, "one")),
+    ///     DiffComponent::Unchanged(&(2, "two"), &(2, "deux")),
+    ///     DiffComponent::Insertion(&(3, "trois"))
+    /// ]);
+    /// ```
+    pub fn by_key<K, F>(a: &'a [T], b: &'a [T], key: F) -> LcsTable<'a, T, impl Fn(&T, &T) -> bool>
+            where K: Eq, F: Fn(&T) -> K {
+        let lengths = {
+            let keys_a: Vec<_> = a.iter().map(&key).collect();
+            let keys_b: Vec<_> = b.iter().map(&key).collect();
+            fill(a.len(), b.len(), |i, j| keys_a[i] == keys_b[j])
+        };
+
+        let eq = move |elem_a: &T, elem_b: &T| key(elem_a) == key(elem_b);
+        LcsTable { lengths: Cow::Owned(lengths), a, b, eq }
+    }
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Constructs a LcsTable for matching between two sequences `a` and `b`, where two elements
+    /// match if `eq` says so. The elements don't need to implement `Eq` at all, so this works for
+    /// types like `f64`.
+    ///
+    /// `eq` should treat matching as an equivalence, like `==` does. Methods that look for every
+    /// distinct longest common subsequence, such as `count_distinct_lcs`, compare elements of `a`
+    /// against each other with it, and other methods assume it's symmetric. A looser comparison,
+    /// such as one with a tolerance, still gives correct lengths and diffs.
+    ///
+    /// Even a comparison that's inconsistent, such as one that gives different answers for the
+    /// same pair, never makes a method panic or loop forever. Diffs always turn `a` into `b`, but
+    /// might not be minimal, and the subsequences found might not be longest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec!["Apple", "banana"];
+    /// let b = vec!["apple", "Cherry"];
+    ///
+    /// let table = LcsTable::new_by(&a, &b, |x: &&str, y: &&str| x.eq_ignore_ascii_case(y));
+    /// assert_eq!(1, table.length());
+    /// ```
+    pub fn new_by(a: &'a [T], b: &'a [T], eq: E) -> LcsTable<'a, T, E> {
+        let lengths = fill(a.len(), b.len(), |i, j| eq(&a[i], &b[j]));
+        LcsTable { lengths: Cow::Owned(lengths), a, b, eq }
+    }
+}
+
+impl<'a> LcsTable<'a, f64> {
+    /// Constructs a LcsTable for matching between two sequences of numbers `a` and `b`, where two
+    /// numbers match if they're within `eps` of each other. See `new_by`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec![1.0, 2.0, 3.0];
+    /// let b = vec![1.01, 2.5, 2.99];
+    ///
+    /// let table = LcsTable::with_tolerance(&a, &b, 0.05);
+    /// assert_eq!(2, table.length());
+    /// ```
+    pub fn with_tolerance(a: &'a [f64], b: &'a [f64], eps: f64)
+            -> LcsTable<'a, f64, impl Fn(&f64, &f64) -> bool> {
+        LcsTable::new_by(a, b, move |x: &f64, y: &f64| (x - y).abs() <= eps)
+    }
+}
+
+impl<'a, T> LcsTable<'a, Option<T>> where T: PartialEq {
+    /// Constructs a LcsTable for matching between two sequences `a` and `b` with gaps in them,
+    /// where `None` marks a position whose value is unknown. A gap never matches anything, not
+    /// even another gap, unlike with `LcsTable::new`, where `None == None`. See `new_by`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec![Some(1), None, Some(2)];
+    /// let b = vec![None, Some(2)];
+    ///
+    /// assert_eq!(2, LcsTable::new(&a, &b).length());
+    /// assert_eq!(1, LcsTable::with_gaps(&a, &b).length());
+    /// ```
+    pub fn with_gaps(a: &'a [Option<T>], b: &'a [Option<T>])
+            -> LcsTable<'a, Option<T>, impl Fn(&Option<T>, &Option<T>) -> bool> {
+        LcsTable::new_by(a, b, |x: &Option<T>, y: &Option<T>| x.is_some() && x == y)
+    }
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Gets the length of the longest common subsequence between `a` and `b`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(2, table.length());
+    /// ```
+    pub fn length(&self) -> usize {
+        self.cell(self.a.len(), self.b.len())
+    }
+
+    /// Gets the length of the longest common subsequence between `a[..i]` and `b[..j]`. These
+    /// are the entries of the table `LcsTable` is built around, which has `a.len() + 1` rows and
+    /// `b.len() + 1` columns.
+    ///
+    /// Panics if `i > a.len()` or `j > b.len()`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(1, table.cell(1, 2));
+    /// assert_eq!(table.length(), table.cell(3, 5));
+    /// ```
+    pub fn cell(&self, i: usize, j: usize) -> usize {
+        // Past the end of a row, the index would just land in the next one.
+        assert!(j <= self.b.len(), "column {} out of range for {} columns", j, self.b.len() + 1);
+        self.lengths[i * (self.b.len() + 1) + j]
+    }
+
+    /// Iterates over the entries of the table one anti-diagonal at a time, as `(i, j, length)`
+    /// where `length` is `cell(i, j)`. The `d`th anti-diagonal holds the entries where `i + j ==
+    /// d`, from the one with the smallest `i` to the one with the largest.
+    ///
+    /// Every entry only depends on entries from earlier anti-diagonals, so this is the order the
+    /// table can be filled in a wavefront, as `new_parallel` does with blocks of entries.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "ab".chars().collect();
+    /// let b: Vec<_> = "b".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let diagonals: Vec<_> = table.antidiagonals().collect();
+    /// assert_eq!(diagonals, vec![
+    ///     vec![(0, 0, 0)],
+    ///     vec![(0, 1, 0), (1, 0, 0)],
+    ///     vec![(1, 1, 0), (2, 0, 0)],
+    ///     vec![(2, 1, 1)]
+    /// ]);
+    /// ```
+    pub fn antidiagonals(&self) -> impl Iterator<Item = Vec<(usize, usize, usize)>> + '_ {
+        let (n, m) = (self.a.len(), self.b.len());
+
+        (0..n + m + 1).map(move |d| {
+            let rows = d.saturating_sub(m)..cmp::min(d, n) + 1;
+            rows.map(|i| (i, d - i, self.cell(i, d - i))).collect()
+        })
+    }
+
+    /// Checks that the table is consistent with `a` and `b`: that it has the right dimensions, that
+    /// its first row and column are zero, and that every other entry follows from its neighbors
+    /// the way it would when building the table. A table that passes gives the same results as one
+    /// built from scratch.
+    ///
+    /// This is meant for tables that didn't come straight from a constructor, such as ones
+    /// restored from a cache. It takes O(n * m) time but allocates nothing, and stops at the first
+    /// inconsistent entry.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// assert!(LcsTable::new(&a, &b).validate());
+    /// ```
+    pub fn validate(&self) -> bool {
+        let (n, m) = (self.a.len(), self.b.len());
+        if self.lengths.len() != (n + 1) * (m + 1) {
+            return false;
+        }
+
+        if (0..m + 1).any(|j| self.cell(0, j) != 0) || (0..n + 1).any(|i| self.cell(i, 0) != 0) {
+            return false;
+        }
+
+        (1..n + 1).all(|i| (1..m + 1).all(|j| {
+            self.cell(i, j) == if self.elems_eq(i - 1, j - 1) {
+                self.cell(i - 1, j - 1) + 1
+            } else {
+                cmp::max(self.cell(i - 1, j), self.cell(i, j - 1))
             }
+        }))
+    }
+
+    /// Whether `a` and `b` are element-wise equal, which is the case exactly when their longest
+    /// common subsequence is all of both of them. Two empty sequences are identical.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abc".chars().collect();
+    /// let b: Vec<_> = "abcd".chars().collect();
+    ///
+    /// assert!(LcsTable::new(&a, &a).is_identical());
+    /// assert!(!LcsTable::new(&a, &b).is_identical());
+    /// ```
+    pub fn is_identical(&self) -> bool {
+        self.a.len() == self.b.len() && self.length() == self.a.len()
+    }
+
+    /// Gets the number of single-element insertions and deletions needed to turn `a` into `b`.
+    /// This is the number of `Insertion` and `Deletion` components in `diff`.
+    ///
+    /// This is the edit distance when only insertions and deletions are allowed, not the
+    /// Levenshtein distance, which also allows substituting one element for another. Changing an
+    /// element counts as two edits here, rather than one.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(4, table.edit_distance());
+    /// ```
+    pub fn edit_distance(&self) -> usize {
+        self.a.len() + self.b.len() - 2 * self.length()
+    }
+
+    /// Counts the insertions, deletions, and unchanged elements in the diff from `a` to `b`. Every
+    /// diff that keeps a longest common subsequence has the same counts, so these come straight
+    /// from the table without computing the diff itself.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::{DiffStats, LcsTable};
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(DiffStats { insertions: 3, deletions: 1, unchanged: 2 }, table.diff_stats());
+    /// ```
+    pub fn diff_stats(&self) -> DiffStats {
+        DiffStats {
+            insertions: self.b.len() - self.length(),
+            deletions: self.a.len() - self.length(),
+            unchanged: self.length()
         }
+    }
 
-        LcsTable { lengths: lengths, a: a, b: b }
+    /// Gets a measure of how similar `a` and `b` are, as a number between 0.0 and 1.0. This is
+    /// the same measure as Python's `difflib.SequenceMatcher.ratio()`: twice the length of the
+    /// longest common subsequence, divided by the total number of elements in `a` and `b`.
+    ///
+    /// Two empty sequences are considered identical, and have a ratio of 1.0.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abcd".chars().collect();
+    /// let b: Vec<_> = "bcde".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(0.75, table.ratio());
+    /// ```
+    pub fn ratio(&self) -> f64 {
+        metrics::ratio(self.length(), self.a.len() + self.b.len())
     }
 
     /// Gets the longest common subsequence between `a` and `b`. Returned elements are in the form
@@ -64,24 +583,65 @@ impl<'a, T> LcsTable<'a, T> where T: Eq {
     /// assert_eq!(vec![(&'a', &'a'), (&'b', &'b'), (&'c', &'c')], lcs);
     /// ```
     pub fn longest_common_subsequence(&self) -> Vec<(&T, &T)> {
+        self.subsequence_indices().into_iter()
+            .map(|(i, j)| (&self.a[i], &self.b[j]))
+            .collect()
+    }
+
+    /// Gets the longest common subsequence between `a` and `b` as a sequence of its own, cloning
+    /// each element out of `a`. This is the same subsequence as `longest_common_subsequence`,
+    /// but the result doesn't borrow from `a` or `b`, so it can outlive them.
+    ///
+    /// With a comparison other than `==`, such as from `by_key`, matching elements of `a` and `b`
+    /// can differ; the ones from `a` are the ones kept.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "a--b---c".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(vec!['a', 'b', 'c'], table.longest_common_subsequence_owned());
+    /// ```
+    pub fn longest_common_subsequence_owned(&self) -> Vec<T> where T: Clone {
+        self.subsequence_indices().into_iter().map(|(i, _)| self.a[i].clone()).collect()
+    }
+
+    /// Gets the positions of the elements of the longest common subsequence between `a` and `b`.
+    /// Returned elements are in the form `(i, j)`, where `a[i]` and `b[j]` are the elements
+    /// `longest_common_subsequence` would return at that point in the subsequence.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "a--b---c".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(vec![(0, 0), (3, 1), (7, 2)], table.subsequence_indices());
+    /// ```
+    pub fn subsequence_indices(&self) -> Vec<(usize, usize)> {
         self.find_lcs(self.a.len(), self.b.len())
     }
 
-    fn find_lcs(&self, i: usize, j: usize) -> Vec<(&T, &T)> {
+    fn find_lcs(&self, i: usize, j: usize) -> Vec<(usize, usize)> {
         if i == 0 || j == 0 {
             return vec![];
         }
 
-        if self.a[i - 1] == self.b[j - 1] {
+        if self.elems_eq(i - 1, j - 1) {
             let mut prefix_lcs = self.find_lcs(i - 1, j - 1);
-            prefix_lcs.push((&self.a[i - 1], &self.b[j - 1]));
+            prefix_lcs.push((i - 1, j - 1));
             prefix_lcs
+        } else if self.cell(i, j - 1) > self.cell(i - 1, j) {
+            self.find_lcs(i, j - 1)
         } else {
-            if self.lengths[i][j - 1] > self.lengths[i - 1][j] {
-                self.find_lcs(i, j - 1)
-            } else {
-                self.find_lcs(i - 1, j)
-            }
+            self.find_lcs(i - 1, j)
         }
     }
 
@@ -89,6 +649,9 @@ impl<'a, T> LcsTable<'a, T> where T: Eq {
     /// `(elem_a, elem_b)`, where `elem_a` is a reference to an element in `a`, `elem_b` is a
     /// reference to an element in `b`, and `elem_a == elem_b`.
     ///
+    /// Without the `std` feature, this returns a `BTreeSet` instead, and so requires `T: Ord`
+    /// rather than `T: Hash`.
+    ///
     /// Example:
     ///
     /// ```
@@ -104,46 +667,298 @@ impl<'a, T> LcsTable<'a, T> where T: Eq {
     /// assert!(subsequences.contains(&vec![(&'g', &'g'), (&'a', &'a')]));
     /// assert!(subsequences.contains(&vec![(&'g', &'g'), (&'c', &'c')]));
     /// ```
+    #[cfg(feature = "std")]
     pub fn longest_common_subsequences(&self) -> HashSet<Vec<(&T, &T)>>
-            where T: Hash {
-        self.find_all_lcs(self.a.len(), self.b.len())
+            where T: Hash + Eq {
+        self.longest_common_subsequences_limited(usize::MAX).0
     }
 
-    fn find_all_lcs(&self, i: usize, j: usize) -> HashSet<Vec<(&T, &T)>>
-            where T: Hash {
-        if i == 0 || j == 0 {
-            let mut ret = HashSet::new();
-            ret.insert(vec![]);
-            return ret;
-        }
-
-        if self.a[i - 1] == self.b[j - 1] {
-            let mut sequences = HashSet::new();
-            for mut lcs in self.find_all_lcs(i - 1, j - 1) {
-                lcs.push((&self.a[i - 1], &self.b[j - 1]));
-                sequences.insert(lcs);
-            }
-            sequences
-        } else {
-            let mut sequences = HashSet::new();
-
-            if self.lengths[i][j - 1] >= self.lengths[i - 1][j] {
-                for lsc in self.find_all_lcs(i, j - 1) {
-                    sequences.insert(lsc);
-                }
-            }
-
-            if self.lengths[i - 1][j] >= self.lengths[i][j - 1] {
-                for lsc in self.find_all_lcs(i - 1, j) {
-                    sequences.insert(lsc);
-                }
-            }
-
-            sequences
-        }
-    }
+    /// Gets up to `max` of the longest common subsequences between `a` and `b`, in the same form
+    /// as `longest_common_subsequences`. The returned flag is true if there were more than `max`.
+    ///
+    /// Some inputs have a number of longest common subsequences exponential in their length, such
+    /// as `abab...` and `baba...`, so this is the safer choice when the inputs aren't trusted.
+    ///
+    /// Without the `std` feature, this returns a `BTreeSet` instead, and so requires `T: Ord`
+    /// rather than `T: Hash`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let (subsequences, truncated) = table.longest_common_subsequences_limited(2);
+    /// assert_eq!(2, subsequences.len());
+    /// assert!(truncated);
+    ///
+    /// let (subsequences, truncated) = table.longest_common_subsequences_limited(3);
+    /// assert_eq!(3, subsequences.len());
+    /// assert!(!truncated);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn longest_common_subsequences_limited(&self, max: usize)
+            -> (HashSet<Vec<(&T, &T)>>, bool) where T: Hash + Eq {
+        self.find_all_lcs(max)
+    }
+
+    /// Gets all longest common subsequences between `a` and `b`. Returned elements are in the form
+    /// `(elem_a, elem_b)`, where `elem_a` is a reference to an element in `a`, `elem_b` is a
+    /// reference to an element in `b`, and `elem_a == elem_b`.
+    ///
+    /// With the `std` feature, this returns a `HashSet` instead, and so requires `T: Hash` rather
+    /// than `T: Ord`.
+    #[cfg(not(feature = "std"))]
+    pub fn longest_common_subsequences(&self) -> BTreeSet<Vec<(&T, &T)>>
+            where T: Ord {
+        self.longest_common_subsequences_limited(usize::MAX).0
+    }
+
+    /// Gets up to `max` of the longest common subsequences between `a` and `b`, in the same form
+    /// as `longest_common_subsequences`. The returned flag is true if there were more than `max`.
+    ///
+    /// With the `std` feature, this returns a `HashSet` instead, and so requires `T: Hash` rather
+    /// than `T: Ord`.
+    #[cfg(not(feature = "std"))]
+    pub fn longest_common_subsequences_limited(&self, max: usize)
+            -> (BTreeSet<Vec<(&T, &T)>>, bool) where T: Ord {
+        self.find_all_lcs(max)
+    }
+
+    /// Gets all longest common subsequences between `a` and `b`, in the same form as
+    /// `longest_common_subsequences`, but as a `Vec` in a deterministic order. Subsequences are
+    /// sorted by their elements, comparing them one after another like strings, so the order
+    /// only depends on the contents of `a` and `b`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.longest_common_subsequences_sorted(), vec![
+    ///     vec![(&'a', &'a'), (&'c', &'c')],
+    ///     vec![(&'g', &'g'), (&'a', &'a')],
+    ///     vec![(&'g', &'g'), (&'c', &'c')]
+    /// ]);
+    /// ```
+    pub fn longest_common_subsequences_sorted(&self) -> Vec<Vec<(&T, &T)>> where T: Ord {
+        let mut subsequences: Vec<_> = self.iter_longest_common_subsequences().collect();
+        subsequences.sort();
+        subsequences
+    }
 
-    /// Computes a diff from `a` to `b`.
+    /// Counts the distinct longest common subsequences between `a` and `b`, without constructing
+    /// them. Like `longest_common_subsequences`, subsequences made of equal elements count once,
+    /// even if they appear at different positions in `a` or `b`.
+    ///
+    /// The number of longest common subsequences can grow exponentially with the length of the
+    /// inputs; if it doesn't fit in a `u128`, this returns `u128::MAX`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(3, table.count_distinct_lcs());
+    /// ```
+    pub fn count_distinct_lcs(&self) -> u128 {
+        let mut total = 1;
+        self.distinct_lcs_counts(|counts| total = counts[self.b.len()]);
+        total
+    }
+
+    /// Counts the distinct longest common subsequences of `a[..i]` and `b[..j]` for every `i` and
+    /// `j`, calling `row` with the counts for each `i` in turn, indexed by `j`.
+    fn distinct_lcs_counts<F>(&self, mut row: F) where F: FnMut(&[u128]) {
+        // `counts[j]` is the count for `a[..i]` and `b[..j]`, and `prev[j]` for `a[..i - 1]`.
+        let mut prev = vec![1u128; self.b.len() + 1];
+        let mut counts = vec![1u128; self.b.len() + 1];
+        row(&prev);
+
+        for i in 1..self.a.len() + 1 {
+            for j in 1..self.b.len() + 1 {
+                let length = self.cell(i, j);
+
+                counts[j] = if self.elems_eq(i - 1, j - 1) {
+                    // Every longest common subsequence here ends by matching these two elements.
+                    prev[j - 1]
+                } else {
+                    // Those from above and those from the left, minus the ones they have in common.
+                    let above = if self.cell(i - 1, j) == length { prev[j] } else { 0 };
+                    let left = if self.cell(i, j - 1) == length { counts[j - 1] } else { 0 };
+                    let both = if self.cell(i - 1, j - 1) == length { prev[j - 1] } else { 0 };
+
+                    // If any count has saturated, the exact union is unknown but at least as big.
+                    if above == u128::MAX || left == u128::MAX {
+                        u128::MAX
+                    } else {
+                        // Only an inconsistent `eq` can make `both` bigger than `left`.
+                        above.saturating_add(left.saturating_sub(both))
+                    }
+                };
+            }
+
+            row(&counts);
+            mem::swap(&mut prev, &mut counts);
+        }
+    }
+
+    /// Lazily iterates over all longest common subsequences between `a` and `b`, in the same form
+    /// as `longest_common_subsequences`. Each distinct subsequence comes up exactly once, so there's
+    /// nothing to deduplicate, and no need for `T: Hash` or `T: Ord`.
+    ///
+    /// Subsequences are found one at a time, with work proportional to the number taken, so this
+    /// can look for one with some property without enumerating all of them.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let starts_with_g = table.iter_longest_common_subsequences()
+    ///     .find(|lcs| *lcs[0].0 == 'g');
+    /// assert!(starts_with_g.is_some());
+    /// assert_eq!(3, table.iter_longest_common_subsequences().count());
+    /// ```
+    pub fn iter_longest_common_subsequences(&self) -> impl Iterator<Item = Vec<(&T, &T)>> {
+        // Each entry is the prefixes of `a` and `b` left to search, along with the end of a
+        // subsequence already chosen, in reverse.
+        let mut stack = vec![(self.a.len(), self.b.len(), Vec::new())];
+
+        // Each subsequence is found exactly once, by always matching its last element against
+        // that element's last occurrence in what's left of `a` and `b`. Every step of the search
+        // leads to at least one subsequence, so the work done is proportional to the number found.
+        iter::from_fn(move || {
+            while let Some((i, j, mut suffix)) = stack.pop() {
+                if self.cell(i, j) == 0 {
+                    suffix.reverse();
+                    return Some(suffix);
+                }
+
+                for (p, q) in self.last_matches(i, j) {
+                    let mut longer = suffix.clone();
+                    longer.push((&self.a[p], &self.b[q]));
+                    stack.push((p, q, longer));
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Gets the `n`th distinct longest common subsequence between `a` and `b`, counting from zero,
+    /// in the order `iter_longest_common_subsequences` finds them. Returns `None` if there are `n`
+    /// or fewer of them, which `count_distinct_lcs` can tell ahead of time.
+    ///
+    /// This skips over whole groups of subsequences at once using their counts, rather than
+    /// finding every subsequence before the `n`th, so any page of results is as cheap to get as
+    /// the first. It takes `O(a.len() * b.len())` space for the counts.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let all: Vec<_> = table.iter_longest_common_subsequences().collect();
+    /// assert_eq!(table.nth_lcs(1), Some(all[1].clone()));
+    /// assert_eq!(table.nth_lcs(3), None);
+    /// ```
+    pub fn nth_lcs(&self, n: usize) -> Option<Vec<(&T, &T)>> {
+        let width = self.b.len() + 1;
+        let mut counts = Vec::with_capacity((self.a.len() + 1) * width);
+        self.distinct_lcs_counts(|row| counts.extend_from_slice(row));
+
+        let (mut i, mut j) = (self.a.len(), self.b.len());
+        let mut n = n as u128;
+        if n >= counts[i * width + j] {
+            return None;
+        }
+
+        // Walk down the same search as `iter_longest_common_subsequences`, skipping the branches
+        // it would have finished before reaching the `n`th subsequence.
+        let mut subsequence = Vec::new();
+        while self.cell(i, j) > 0 {
+            let mut next = None;
+            for (p, q) in self.last_matches(i, j).into_iter().rev() {
+                let count = counts[p * width + q];
+                if n < count {
+                    next = Some((p, q));
+                    break;
+                }
+
+                n -= count;
+            }
+
+            // There's always a branch left, unless `eq` is inconsistent.
+            let (p, q) = next?;
+            subsequence.push((&self.a[p], &self.b[q]));
+            i = p;
+            j = q;
+        }
+
+        subsequence.reverse();
+        Some(subsequence)
+    }
+
+    /// Finds up to `max` distinct longest common subsequences, and whether there were more.
+    fn find_all_lcs<'b, S>(&'b self, max: usize) -> (S, bool)
+            where S: Default + Extend<Vec<(&'b T, &'b T)>> {
+        let mut subsequences = self.iter_longest_common_subsequences();
+
+        let mut found = S::default();
+        found.extend(subsequences.by_ref().take(max));
+        (found, subsequences.next().is_some())
+    }
+
+    /// Finds the positions `(p, q)` that can be the last match of a longest common subsequence of
+    /// `a[..i]` and `b[..j]`, where `a[p]` and `b[q]` are the last occurrences of their element.
+    /// There's one for each distinct element a longest common subsequence can end with.
+    fn last_matches(&self, i: usize, j: usize) -> Vec<(usize, usize)> {
+        let length = self.cell(i, j);
+        let mut matches = Vec::new();
+
+        for p in (0..i).rev() {
+            // No subsequence ending this early in `a` can be long enough.
+            if self.cell(p + 1, j) < length {
+                break;
+            }
+
+            let elem = &self.a[p];
+            if (p + 1..i).any(|k| (self.eq)(&self.a[k], elem)) {
+                continue;
+            }
+
+            if let Some(q) = (0..j).rev().find(|&q| (self.eq)(elem, &self.b[q])) {
+                if self.cell(p, q) + 1 == length {
+                    matches.push((p, q));
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// Computes a diff from `a` to `b`. The diff borrows from `a` and `b`, not from the table, so
+    /// it can outlive the table.
     ///
     /// # Example
     ///
@@ -162,55 +977,333 @@ impl<'a, T> LcsTable<'a, T> where T: Eq {
     ///     DiffComponent::Insertion(&'c')
     /// ]);
     /// ```
-    pub fn diff(&self) -> Vec<DiffComponent<&T>> {
-        self.compute_diff(self.a.len(), self.b.len())
+    pub fn diff(&self) -> Diff<'a, T> {
+        self.diff_iter().collect()
     }
 
-    fn compute_diff(&self, i: usize, j: usize) -> Vec<DiffComponent<&T>> {
-        if i == 0 && j == 0 {
-            return vec![];
-        }
+    /// Lazily computes a diff from `a` to `b`, yielding the same components as `diff`.
+    ///
+    /// Backtracking through the table naturally finds the diff back-to-front, so the path through
+    /// the table is still found up front. But the components themselves are produced one at a
+    /// time, which is useful when streaming a large diff somewhere else.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let mut diff = table.diff_iter();
+    /// assert_eq!(diff.next(), Some(DiffComponent::Unchanged(&'a', &'a')));
+    /// assert_eq!(diff.next(), Some(DiffComponent::Deletion(&'x')));
+    /// ```
+    pub fn diff_iter(&self) -> impl Iterator<Item = DiffComponent<&'a T>> {
+        self.components(self.diff_steps(TieBias::default()))
+    }
 
-        enum DiffType {
-            Insertion,
-            Unchanged,
-            Deletion
-        }
+    /// Computes a diff from `a` to `b` like `diff`, with `bias` deciding which way to go when
+    /// there's a choice between an insertion and a deletion that lead to equally small diffs.
+    ///
+    /// The diff is found by backtracking from the end of `a` and `b`, so the component picked on a
+    /// tie ends up *after* the other one. `TieBias::PreferDeletion`, which is what `diff` uses,
+    /// tends to put insertions before deletions, and `TieBias::PreferInsertion` the opposite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable, TieBias};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "ayb".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_with_bias(TieBias::PreferDeletion), vec![
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Insertion(&'y'),
+    ///     DiffComponent::Deletion(&'x'),
+    ///     DiffComponent::Unchanged(&'b', &'b')
+    /// ]);
+    /// assert_eq!(table.diff_with_bias(TieBias::PreferInsertion), vec![
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Deletion(&'x'),
+    ///     DiffComponent::Insertion(&'y'),
+    ///     DiffComponent::Unchanged(&'b', &'b')
+    /// ]);
+    /// ```
+    pub fn diff_with_bias(&self, bias: TieBias) -> Vec<DiffComponent<&'a T>> {
+        self.components(self.diff_steps(bias)).collect()
+    }
 
-        let diff_type = if i == 0 {
-            DiffType::Insertion
-        } else if j == 0 {
-            DiffType::Deletion
-        } else if self.a[i - 1] == self.b[j - 1] {
-            DiffType::Unchanged
-        } else if self.lengths[i][j - 1] > self.lengths[i - 1][j] {
-            DiffType::Insertion
-        } else {
-            DiffType::Deletion
-        };
+    /// Turns the steps from `diff_steps` into diff components, front to back.
+    fn components(&self, steps: Vec<DiffType>) -> impl Iterator<Item = DiffComponent<&'a T>> {
+        let (a, b) = (self.a, self.b);
+        let (mut i, mut j) = (0, 0);
 
-        let (to_add, mut rest_diff) = match diff_type {
+        steps.into_iter().rev().map(move |step| match step {
             DiffType::Insertion => {
-                (DiffComponent::Insertion(&self.b[j - 1]),
-                    self.compute_diff(i, j - 1))
+                j += 1;
+                DiffComponent::Insertion(&b[j - 1])
             },
 
             DiffType::Unchanged => {
-                (DiffComponent::Unchanged(&self.a[i - 1], &self.b[j - 1]),
-                    self.compute_diff(i - 1, j - 1))
+                i += 1;
+                j += 1;
+                DiffComponent::Unchanged(&a[i - 1], &b[j - 1])
             },
 
             DiffType::Deletion => {
-                (DiffComponent::Deletion(&self.a[i - 1]),
-                    self.compute_diff(i - 1, j))
+                i += 1;
+                DiffComponent::Deletion(&a[i - 1])
             }
-        };
+        })
+    }
+
+    /// Computes a diff from `a` to `b` like `diff`, but with each element cloned out of `a` or `b`.
+    /// The result doesn't borrow from the table or its inputs, so it can outlive both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_cloned(), vec![
+    ///     DiffComponent::Unchanged('a', 'a'),
+    ///     DiffComponent::Deletion('x'),
+    ///     DiffComponent::Unchanged('b', 'b'),
+    ///     DiffComponent::Insertion('c')
+    /// ]);
+    /// ```
+    pub fn diff_cloned(&self) -> Vec<DiffComponent<T>> where T: Clone {
+        self.diff_iter()
+            .map(|component| match component {
+                DiffComponent::Insertion(elem_b) => DiffComponent::Insertion(elem_b.clone()),
+                DiffComponent::Unchanged(elem_a, elem_b) => {
+                    DiffComponent::Unchanged(elem_a.clone(), elem_b.clone())
+                },
+                DiffComponent::Deletion(elem_a) => DiffComponent::Deletion(elem_a.clone())
+            })
+            .collect()
+    }
+
+    /// Computes a diff from `a` to `b` with each element cloned. Despite the name, this doesn't
+    /// consume the table.
+    #[deprecated(note = "use `diff_cloned` instead")]
+    pub fn into_diff_owned(&self) -> Vec<DiffComponent<T>> where T: Clone {
+        self.diff_cloned()
+    }
 
-        rest_diff.push(to_add);
-        rest_diff
+    /// Lines up `a` and `b` according to the diff from `a` to `b`. The two returned vectors have
+    /// the same length, one entry per diff component: unchanged elements are side by side, and an
+    /// inserted or deleted element is opposite a `None` gap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// let (aligned_a, aligned_b) = table.aligned();
+    /// assert_eq!(aligned_a, vec![Some(&'a'), Some(&'x'), Some(&'b'), None]);
+    /// assert_eq!(aligned_b, vec![Some(&'a'), None, Some(&'b'), Some(&'c')]);
+    /// ```
+    pub fn aligned(&self) -> (Vec<Option<&T>>, Vec<Option<&T>>) {
+        self.side_by_side().into_iter().unzip()
+    }
+
+    /// Lines up `a` and `b` like `aligned`, but as rows of a two-column view instead of two
+    /// separate columns. Each row has the element from `a` on the left and the one from `b` on the
+    /// right: an insertion is `(None, Some(elem_b))`, and a deletion is `(Some(elem_a), None)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.side_by_side(), vec![
+    ///     (Some(&'a'), Some(&'a')),
+    ///     (Some(&'x'), None),
+    ///     (Some(&'b'), Some(&'b')),
+    ///     (None, Some(&'c'))
+    /// ]);
+    /// ```
+    pub fn side_by_side(&self) -> Vec<(Option<&T>, Option<&T>)> {
+        self.diff_iter()
+            .map(|component| match component {
+                DiffComponent::Insertion(elem_b) => (None, Some(elem_b)),
+                DiffComponent::Unchanged(elem_a, elem_b) => (Some(elem_a), Some(elem_b)),
+                DiffComponent::Deletion(elem_a) => (Some(elem_a), None)
+            })
+            .collect()
+    }
+
+    /// Whether `a[i]` and `b[j]` match.
+    fn elems_eq(&self, i: usize, j: usize) -> bool {
+        (self.eq)(&self.a[i], &self.b[j])
+    }
+
+    /// Backtracks through the table, returning the kind of each diff component from last to first.
+    fn diff_steps(&self, bias: TieBias) -> Vec<DiffType> {
+        let mut steps = Vec::with_capacity(self.a.len() + self.b.len());
+        let (mut i, mut j) = (self.a.len(), self.b.len());
+
+        while i > 0 || j > 0 {
+            let diff_type = if i == 0 {
+                DiffType::Insertion
+            } else if j == 0 {
+                DiffType::Deletion
+            } else if self.elems_eq(i - 1, j - 1) {
+                DiffType::Unchanged
+            } else if self.cell(i, j - 1) > self.cell(i - 1, j) ||
+                    (bias == TieBias::PreferInsertion &&
+                     self.cell(i, j - 1) == self.cell(i - 1, j)) {
+                DiffType::Insertion
+            } else {
+                DiffType::Deletion
+            };
+
+            match diff_type {
+                DiffType::Insertion => j -= 1,
+                DiffType::Unchanged => { i -= 1; j -= 1; },
+                DiffType::Deletion => i -= 1
+            }
+
+            steps.push(diff_type);
+        }
+
+        steps
+    }
+}
+
+/// Formats a component as a line of a patch: the element, prefixed by `+` for an insertion, `-` for
+/// a deletion, or a space if it's unchanged. An unchanged component shows its element from `a`.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+///
+/// assert_eq!("+x", DiffComponent::Insertion('x').to_string());
+/// assert_eq!(" y", DiffComponent::Unchanged('y', 'y').to_string());
+/// assert_eq!("-z", DiffComponent::Deletion('z').to_string());
+/// ```
+impl<T> fmt::Display for DiffComponent<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DiffComponent::Insertion(ref elem_b) => write!(f, "+{}", elem_b),
+            DiffComponent::Unchanged(ref elem_a, _) => write!(f, " {}", elem_a),
+            DiffComponent::Deletion(ref elem_a) => write!(f, "-{}", elem_a)
+        }
+    }
+}
+
+// Cloning only copies the references to `a` and `b`, so it shouldn't require `T: Clone`, as
+// `#[derive(Clone)]` would.
+impl<'a, T, E> Clone for LcsTable<'a, T, E> where E: Clone {
+    fn clone(&self) -> LcsTable<'a, T, E> {
+        LcsTable { lengths: self.lengths.clone(), a: self.a, b: self.b, eq: self.eq.clone() }
     }
 }
 
+// The comparison usually can't be printed, so this leaves it out.
+impl<'a, T, E> fmt::Debug for LcsTable<'a, T, E> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LcsTable")
+            .field("lengths", &self.lengths)
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Default for LcsTable<'a, T> where T: Eq {
+    fn default() -> LcsTable<'a, T> {
+        LcsTable::empty()
+    }
+}
+
+/// Two tables are equal if they were built from equal inputs. The table itself follows from those.
+impl<'a, T> PartialEq for LcsTable<'a, T> where T: Eq {
+    fn eq(&self, other: &LcsTable<'a, T>) -> bool {
+        self.a == other.a && self.b == other.b
+    }
+}
+
+impl<'a, T> Eq for LcsTable<'a, T> where T: Eq {}
+
+/// Fills in a table of longest common subsequence lengths between sequences of lengths `n` and
+/// `m`, where `eq(i, j)` says whether the `i`th element of the first matches the `j`th of the
+/// second.
+fn fill<F>(n: usize, m: usize, eq: F) -> Vec<usize> where F: Fn(usize, usize) -> bool {
+    let mut lengths = Vec::new();
+    fill_into(&mut lengths, n, m, eq);
+    lengths
+}
+
+/// Like `fill`, but reuses the allocation in `lengths`, whatever its contents.
+fn fill_into<F>(lengths: &mut Vec<usize>, n: usize, m: usize, eq: F)
+        where F: Fn(usize, usize) -> bool {
+    let width = m + 1;
+
+    // Only the first row and column need to start out as zero; every other entry is written
+    // before it's read.
+    lengths.clear();
+    lengths.resize((n + 1) * width, 0);
+
+    for i in 0..n {
+        let (above, current) = lengths[i * width..(i + 2) * width].split_at_mut(width);
+
+        for j in 0..m {
+            current[j + 1] = if eq(i, j) {
+                1 + above[j]
+            } else {
+                cmp::max(current[j], above[j + 1])
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum DiffType {
+    Insertion,
+    Unchanged,
+    Deletion
+}
+
+/// Generates `count` pairs of short random strings over a small alphabet, for use in tests.
+#[cfg(test)]
+fn random_strings(seed: u64, count: usize) -> Vec<(Vec<char>, Vec<char>)> {
+    let mut state = seed;
+    let mut next = move |bound: u64| {
+        // xorshift64; good enough to shake out edge cases without pulling in a dependency.
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    };
+
+    (0..count).map(|_| {
+        let len_a = next(16) as usize;
+        let len_b = next(16) as usize;
+        let a = (0..len_a).map(|_| (b'a' + next(3) as u8) as char).collect();
+        let b = (0..len_b).map(|_| (b'a' + next(3) as u8) as char).collect();
+        (a, b)
+    }).collect()
+}
+
 #[test]
 fn test_lcs_table() {
     // Example taken from:
@@ -222,13 +1315,242 @@ fn test_lcs_table() {
 
     let actual_lengths = LcsTable::new(&a, &b).lengths;
     let expected_lengths = vec![
-        vec![0, 0, 0, 0, 0, 0],
-        vec![0, 0, 1, 1, 1, 1],
-        vec![0, 1, 1, 1, 2, 2],
-        vec![0, 1, 1, 2, 2, 2]
+        0, 0, 0, 0, 0, 0,
+        0, 0, 1, 1, 1, 1,
+        0, 1, 1, 1, 2, 2,
+        0, 1, 1, 2, 2, 2
     ];
 
-    assert_eq!(expected_lengths, actual_lengths);
+    assert_eq!(expected_lengths, actual_lengths.into_owned());
+}
+
+#[test]
+fn test_empty() {
+    let table: LcsTable<char> = LcsTable::default();
+    assert_eq!(LcsTable::empty(), table);
+
+    assert_eq!(0, table.length());
+    assert!(table.is_identical());
+    assert!(table.diff().is_empty());
+    assert_eq!(1, table.iter_longest_common_subsequences().count());
+    assert!(table.validate());
+}
+
+#[test]
+fn test_new_by() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x4a7f_3c2e_91d8_b605, 100) {
+        let table = LcsTable::new_by(&a, &b, |x: &char, y: &char| x == y);
+        assert_eq!(LcsTable::new(&a, &b).diff(), table.diff());
+    }
+
+    let a = vec![0.0, 1.0, f64::NAN, 2.0];
+    let b = vec![0.1, f64::NAN, 1.95];
+
+    let table = LcsTable::with_tolerance(&a, &b, 0.1);
+    assert_eq!(table.longest_common_subsequence(), vec![(&0.0, &0.1), (&2.0, &1.95)]);
+}
+
+#[test]
+fn test_with_gaps() {
+    let a = vec![None, Some('a'), None, None, Some('b')];
+    let b = vec![None, None, Some('b'), None];
+
+    let table = LcsTable::with_gaps(&a, &b);
+    assert_eq!(table.longest_common_subsequence(), vec![(&Some('b'), &Some('b'))]);
+    assert_eq!(table.count_distinct_lcs(), 1);
+    assert!(table.diff().iter().all(|component| match *component {
+        DiffComponent::Unchanged(elem_a, elem_b) => elem_a.is_some() && elem_b.is_some(),
+        _ => true
+    }));
+}
+
+#[test]
+fn test_inconsistent_comparator() {
+    use std::cell::Cell;
+
+    let a: Vec<_> = "abcabcabcabc".chars().collect();
+    let b: Vec<_> = "cbacbacbacba".chars().collect();
+
+    // Says yes and no in turns, no matter what it's asked.
+    let answer = Cell::new(false);
+    let flaky = |_: &char, _: &char| {
+        answer.set(!answer.get());
+        answer.get()
+    };
+    let asymmetric = |x: &char, y: &char| x <= y;
+
+    fn check<E>(table: LcsTable<char, E>) where E: Fn(&char, &char) -> bool {
+        let diff = table.diff();
+        let a_side: Vec<_> = diff.iter().filter_map(|component| match *component {
+            DiffComponent::Insertion(_) => None,
+            DiffComponent::Unchanged(elem_a, _) | DiffComponent::Deletion(elem_a) => Some(elem_a)
+        }).collect();
+        let b_side: Vec<_> = diff.iter().filter_map(|component| match *component {
+            DiffComponent::Unchanged(_, elem_b) | DiffComponent::Insertion(elem_b) => Some(elem_b),
+            DiffComponent::Deletion(_) => None
+        }).collect();
+
+        assert_eq!(a_side, table.a.iter().collect::<Vec<_>>());
+        assert_eq!(b_side, table.b.iter().collect::<Vec<_>>());
+
+        table.longest_common_subsequence();
+        table.count_distinct_lcs();
+        table.diff_with_bias(TieBias::PreferInsertion);
+        table.iter_longest_common_subsequences().take(1000).count();
+    }
+
+    check(LcsTable::new_by(&a, &b, flaky));
+    check(LcsTable::new_by(&a, &b, asymmetric));
+}
+
+#[test]
+fn test_longest_common_subsequences_sorted() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x0e5c_7a91_b24f_d836, 100) {
+        let table = LcsTable::new(&a, &b);
+        let sorted = table.longest_common_subsequences_sorted();
+
+        assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(sorted.len() as u128, table.count_distinct_lcs());
+        assert_eq!(sorted, LcsTable::new(&a, &b).longest_common_subsequences_sorted());
+    }
+}
+
+#[test]
+fn test_nth_lcs() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x6f13_d8a2_c5e7_0b49, 100) {
+        let table = LcsTable::new(&a, &b);
+        let all: Vec<_> = table.iter_longest_common_subsequences().collect();
+
+        for (n, subsequence) in all.iter().enumerate() {
+            assert_eq!(table.nth_lcs(n).as_ref(), Some(subsequence));
+        }
+
+        assert_eq!(table.nth_lcs(all.len()), None);
+    }
+}
+
+#[test]
+fn test_longest_common_subsequence_owned() {
+    use random_strings;
+
+    for (a, b) in random_strings(0xf4a9_1d36_8c0e_b752, 100) {
+        let table = LcsTable::new(&a, &b);
+        let borrowed: Vec<_> = table.longest_common_subsequence().into_iter()
+            .map(|(elem_a, _)| *elem_a)
+            .collect();
+
+        assert_eq!(table.longest_common_subsequence_owned(), borrowed);
+    }
+}
+
+#[test]
+fn test_try_new() {
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+
+    assert_eq!(Ok(LcsTable::new(&a, &b)), LcsTable::try_new(&a, &b));
+    assert_eq!(Err(LcsError::TooLarge { len_a: 3, len_b: 5 }),
+               LcsTable::try_new_with_limit(&a, &b, 23));
+
+    // Far more than any machine's address space.
+    let huge = vec![0u8; 1 << 24];
+    assert_eq!(Err(LcsError::AllocationFailed { len_a: 1 << 24, len_b: 1 << 24 }),
+               LcsTable::try_new(&huge, &huge));
+}
+
+#[test]
+fn test_from_sequences() {
+    struct Chars(Vec<char>);
+
+    impl AsRef<[char]> for Chars {
+        fn as_ref(&self) -> &[char] {
+            &self.0
+        }
+    }
+
+    let a = Chars("gac".chars().collect());
+    let b: Vec<_> = "agcat".chars().collect();
+
+    let table = LcsTable::from_sequences(&a, &b);
+    assert_eq!(table.lengths, LcsTable::new(&a.0, &b).lengths);
+    assert_eq!(2, LcsTable::from_sequences(&b[..], &a).length());
+}
+
+#[test]
+fn test_cell() {
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    for i in 0..a.len() + 1 {
+        for j in 0..b.len() + 1 {
+            assert_eq!(table.lengths[i * (b.len() + 1) + j], table.cell(i, j));
+        }
+    }
+}
+
+#[test]
+fn test_antidiagonals() {
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let diagonals: Vec<_> = table.antidiagonals().collect();
+    assert_eq!(a.len() + b.len() + 1, diagonals.len());
+
+    let mut seen = 0;
+    for (d, diagonal) in diagonals.iter().enumerate() {
+        assert!(diagonal.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        for &(i, j, length) in diagonal {
+            assert_eq!(d, i + j);
+            assert_eq!(table.cell(i, j), length);
+            seen += 1;
+        }
+    }
+
+    assert_eq!((a.len() + 1) * (b.len() + 1), seen);
+}
+
+#[test]
+fn test_validate() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x6c8e_9cf5_7043_2d0b, 100) {
+        let mut table = LcsTable::new(&a, &b);
+        assert!(table.validate());
+
+        let last = table.lengths.len() - 1;
+        table.lengths.to_mut()[last] += 1;
+        assert!(!table.validate());
+
+        table.lengths.to_mut().pop();
+        assert!(!table.validate());
+    }
+
+    // A table built for other inputs.
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+    let c: Vec<_> = "tacga".chars().collect();
+
+    let mismatched = LcsTable { b: &c, ..LcsTable::new(&a, &b) };
+    assert!(!mismatched.validate());
+}
+
+#[test]
+#[should_panic]
+fn test_cell_out_of_range() {
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+
+    // This would be in range if the row just wrapped around into the next one.
+    LcsTable::new(&a, &b).cell(1, 6);
 }
 
 #[test]
@@ -254,6 +1576,41 @@ fn test_longest_common_subsequences() {
     assert!(subsequences.contains(&vec![(&'g', &'g'), (&'c', &'c')]));
 }
 
+#[test]
+fn test_iter_longest_common_subsequences() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x1f83_d9ab_fb41_bd6b, 200) {
+        let table = LcsTable::new(&a, &b);
+        let subsequences: Vec<_> = table.iter_longest_common_subsequences().collect();
+
+        assert_eq!(table.count_distinct_lcs(), subsequences.len() as u128);
+        assert!(subsequences.iter().all(|lcs| lcs.len() == table.length()));
+        assert!(subsequences.iter().enumerate().all(|(k, lcs)| !subsequences[..k].contains(lcs)));
+    }
+}
+
+#[test]
+fn test_longest_common_subsequences_limited() {
+    let a: Vec<_> = "ab#".repeat(40).chars().collect();
+    let b: Vec<_> = "ba#".repeat(40).chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let (subsequences, truncated) = table.longest_common_subsequences_limited(1000);
+    assert_eq!(1000, subsequences.len());
+    assert!(truncated);
+    assert!(subsequences.iter().all(|lcs| lcs.len() == table.length()));
+
+    // Only one subsequence, but exponentially many paths through the table lead to it.
+    let a: Vec<_> = "cxxxxxxxxxxxxxxxxxxxxxxxxxxxxx".chars().collect();
+    let b: Vec<_> = "cyyyyyyyyyyyyyyyyyyyyyyyyyyyyy".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let (subsequences, truncated) = table.longest_common_subsequences_limited(1);
+    assert_eq!(1, subsequences.len());
+    assert!(!truncated);
+}
+
 #[test]
 fn test_diff() {
     use DiffComponent::*;
@@ -270,3 +1627,267 @@ fn test_diff() {
         Insertion(&'c')
     ]);
 }
+
+#[test]
+fn test_edit_distance() {
+    let empty: Vec<char> = vec![];
+    let a: Vec<_> = "abc".chars().collect();
+
+    assert_eq!(0, LcsTable::new(&empty, &empty).edit_distance());
+    assert_eq!(3, LcsTable::new(&a, &empty).edit_distance());
+    assert_eq!(3, LcsTable::new(&empty, &a).edit_distance());
+    assert_eq!(0, LcsTable::new(&a, &a).edit_distance());
+
+    // A substitution counts as a deletion and an insertion.
+    assert_eq!(2, LcsTable::new(&['a'], &['b']).edit_distance());
+
+    let axb: Vec<_> = "axb".chars().collect();
+    let table = LcsTable::new(&axb, &a);
+    let changes = table.diff().iter()
+        .filter(|component| !matches!(**component, DiffComponent::Unchanged(_, _)))
+        .count();
+    assert_eq!(2, table.edit_distance());
+    assert_eq!(changes, table.edit_distance());
+}
+
+#[test]
+fn test_is_identical() {
+    let empty: Vec<char> = vec![];
+    let a: Vec<_> = "abc".chars().collect();
+    let b: Vec<_> = "acb".chars().collect();
+
+    assert!(LcsTable::new(&empty, &empty).is_identical());
+    assert!(LcsTable::new(&a, &a).is_identical());
+    assert!(!LcsTable::new(&a, &empty).is_identical());
+    assert!(!LcsTable::new(&a, &b).is_identical());
+}
+
+#[test]
+fn test_ratio() {
+    let empty: Vec<char> = vec![];
+    let a: Vec<_> = "abc".chars().collect();
+    let b: Vec<_> = "xyz".chars().collect();
+
+    assert_eq!(1.0, LcsTable::new(&empty, &empty).ratio());
+    assert_eq!(0.0, LcsTable::new(&a, &empty).ratio());
+    assert_eq!(0.0, LcsTable::new(&a, &b).ratio());
+    assert_eq!(1.0, LcsTable::new(&a, &a).ratio());
+}
+
+#[test]
+fn test_subsequence_indices() {
+    let a: Vec<_> = "XXXaXXXbXXXc".chars().collect();
+    let b: Vec<_> = "YYaYYbYYc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let indices = table.subsequence_indices();
+    assert_eq!(vec![(3, 2), (7, 5), (11, 8)], indices);
+
+    let lcs: Vec<_> = indices.into_iter().map(|(i, j)| (&a[i], &b[j])).collect();
+    assert_eq!(table.longest_common_subsequence(), lcs);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_diff_component_serde() {
+    use DiffComponent::*;
+
+    let diff = vec![Unchanged('a', 'a'), Deletion('x'), Insertion('c')];
+    let json = serde_json::to_string(&diff).unwrap();
+    assert_eq!(json, r#"[{"Unchanged":["a","a"]},{"Deletion":"x"},{"Insertion":"c"}]"#);
+
+    let round_tripped: Vec<DiffComponent<char>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(diff, round_tripped);
+}
+
+#[test]
+fn test_diff_iter() {
+    let a: Vec<_> = "XXXaXXXbXXXc".chars().collect();
+    let b: Vec<_> = "YYaYYbYYc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let streamed: Vec<_> = table.diff_iter().collect();
+    assert_eq!(a.len() + b.len() - table.length(), streamed.len());
+
+    let unchanged: Vec<_> = streamed.into_iter()
+        .filter_map(|component| match component {
+            DiffComponent::Unchanged(elem_a, elem_b) => Some((elem_a, elem_b)),
+            _ => None
+        })
+        .collect();
+    assert_eq!(table.longest_common_subsequence(), unchanged);
+}
+
+#[test]
+fn test_diff_stats() {
+    let a: Vec<_> = "XXXaXXXbXXXc".chars().collect();
+    let b: Vec<_> = "YYaYYbYYc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let mut counted = DiffStats::default();
+    for component in table.diff() {
+        match component {
+            DiffComponent::Insertion(_) => counted.insertions += 1,
+            DiffComponent::Unchanged(_, _) => counted.unchanged += 1,
+            DiffComponent::Deletion(_) => counted.deletions += 1
+        }
+    }
+
+    assert_eq!(counted, table.diff_stats());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_into_diff_owned() {
+    let diff = {
+        let a = vec![String::from("x"), String::from("y")];
+        let b = vec![String::from("y"), String::from("z")];
+
+        let table = LcsTable::new(&a, &b);
+        table.into_diff_owned()
+    };
+
+    assert_eq!(diff, vec![
+        DiffComponent::Deletion(String::from("x")),
+        DiffComponent::Unchanged(String::from("y"), String::from("y")),
+        DiffComponent::Insertion(String::from("z"))
+    ]);
+}
+
+#[test]
+fn test_diff_cloned() {
+    for (a, b) in random_strings(0x3c6e_f372_fe94_f82b, 200) {
+        let table = LcsTable::new(&a, &b);
+        let cloned = table.diff_cloned();
+        let borrowed = table.diff();
+
+        assert_eq!(borrowed.len(), cloned.len());
+        for (borrowed, cloned) in borrowed.into_iter().zip(cloned) {
+            assert_eq!(cloned, match borrowed {
+                DiffComponent::Insertion(elem_b) => DiffComponent::Insertion(*elem_b),
+                DiffComponent::Unchanged(elem_a, elem_b) => {
+                    DiffComponent::Unchanged(*elem_a, *elem_b)
+                },
+                DiffComponent::Deletion(elem_a) => DiffComponent::Deletion(*elem_a)
+            });
+        }
+    }
+}
+
+#[test]
+fn test_diff_with_bias() {
+    use {apply, random_strings};
+
+    for (a, b) in random_strings(0xe703_7ed1_a0b4_28db, 200) {
+        let table = LcsTable::new(&a, &b);
+        assert_eq!(table.diff(), table.diff_with_bias(TieBias::PreferDeletion));
+
+        let diff = table.diff_with_bias(TieBias::PreferInsertion);
+        assert_eq!(Ok(b.clone()), apply(&a, &diff));
+        assert_eq!(table.length(), diff.iter()
+            .filter(|component| matches!(**component, DiffComponent::Unchanged(_, _)))
+            .count());
+    }
+}
+
+#[test]
+fn test_aligned() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x2127_599b_f432_5c37, 200) {
+        let table = LcsTable::new(&a, &b);
+        let (aligned_a, aligned_b) = table.aligned();
+
+        assert_eq!(aligned_a.len(), aligned_b.len());
+        assert_eq!(a, aligned_a.iter().filter_map(|elem| elem.cloned()).collect::<Vec<_>>());
+        assert_eq!(b, aligned_b.iter().filter_map(|elem| elem.cloned()).collect::<Vec<_>>());
+        assert!(aligned_a.iter().zip(&aligned_b).all(|pair| match pair {
+            (&Some(elem_a), &Some(elem_b)) => elem_a == elem_b,
+            (&None, &None) => false,
+            _ => true
+        }));
+    }
+}
+
+#[test]
+fn test_side_by_side() {
+    let a: Vec<_> = "ab".chars().collect();
+    let b: Vec<_> = "bc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.side_by_side(), vec![
+        (Some(&'a'), None),
+        (Some(&'b'), Some(&'b')),
+        (None, Some(&'c'))
+    ]);
+
+    let (aligned_a, aligned_b) = table.aligned();
+    let zipped: Vec<_> = aligned_a.into_iter().zip(aligned_b).collect();
+    assert_eq!(table.side_by_side(), zipped);
+}
+
+#[test]
+fn test_clone_and_eq() {
+    // Not `Clone`, to make sure cloning a table doesn't need to clone the elements.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Elem(char);
+
+    let a: Vec<_> = "gac".chars().map(Elem).collect();
+    let b: Vec<_> = "agcat".chars().map(Elem).collect();
+    let other_b: Vec<_> = "agcat".chars().map(Elem).collect();
+
+    let table = LcsTable::new(&a, &b);
+    let cloned = table.clone();
+    assert_eq!(table, cloned);
+    assert_eq!(table.diff(), cloned.diff());
+
+    assert_eq!(table, LcsTable::new(&a, &other_b));
+    assert!(table != LcsTable::new(&b, &a));
+}
+
+#[test]
+fn test_by_key() {
+    let a = vec![(1, 'a'), (2, 'b'), (3, 'c')];
+    let b = vec![(1, 'x'), (3, 'c'), (4, 'd')];
+
+    let table = LcsTable::by_key(&a, &b, |&(id, _)| id);
+    assert_eq!(2, table.length());
+    assert_eq!(vec![(&a[0], &b[0]), (&a[2], &b[1])], table.longest_common_subsequence());
+    assert_eq!(1, LcsTable::new(&a, &b).length());
+}
+
+#[test]
+fn test_count_distinct_lcs() {
+    let empty: Vec<char> = vec![];
+    assert_eq!(1, LcsTable::new(&empty, &empty).count_distinct_lcs());
+
+    for (a, b) in random_strings(0xd6e8_feb8_6659_fd93, 300) {
+        let table = LcsTable::new(&a, &b);
+        assert_eq!(table.longest_common_subsequences().len() as u128, table.count_distinct_lcs());
+    }
+
+    // These have 80 choose 40 longest common subsequences, far too many to enumerate.
+    let a: Vec<_> = "ab#".repeat(40).chars().collect();
+    let b: Vec<_> = "ba#".repeat(40).chars().collect();
+    assert_eq!(107_507_208_733_336_176_461_620, LcsTable::new(&a, &b).count_distinct_lcs());
+
+    // And with 140 choose 70, the count has to saturate.
+    let a: Vec<_> = "ab#".repeat(70).chars().collect();
+    let b: Vec<_> = "ba#".repeat(70).chars().collect();
+    assert_eq!(u128::MAX, LcsTable::new(&a, &b).count_distinct_lcs());
+}
+
+#[test]
+fn test_diff_component_display() {
+    use std::fmt::Write;
+
+    let a = ["one", "two", "three"];
+    let b = ["one", "2", "three"];
+
+    let mut rendered = String::new();
+    for component in LcsTable::new(&a, &b).diff() {
+        writeln!(rendered, "{}", component).unwrap();
+    }
+
+    assert_eq!(" one\n+2\n-two\n three\n", rendered);
+}
@@ -0,0 +1,146 @@
+//! Three-way merging of two sequences derived from a common base.
+
+use LcsTable;
+use prelude::*;
+
+/// A region of the result of `merge3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeChunk<T> {
+    /// A region both sides agree on, either because neither changed it, only one side changed it,
+    /// or both made the same change.
+    Merged(Vec<T>),
+
+    /// A region of the base that the two sides changed in different ways.
+    Conflict { left: Vec<T>, right: Vec<T> }
+}
+
+/// Merges the changes from `base` to `left` with the changes from `base` to `right`.
+///
+/// Both sides are diffed against `base`. Wherever an element of `base` was kept by both sides,
+/// the two diffs are in sync. Between those points, if only one side changed anything, its
+/// version wins; if both sides changed the same region differently, the result has a conflict.
+/// This errs on the side of reporting conflicts: two insertions at the same point in `base`
+/// conflict unless they're identical.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{merge3, MergeChunk};
+///
+/// let base: Vec<_> = "abcde".chars().collect();
+/// let left: Vec<_> = "aXcde".chars().collect();
+/// let right: Vec<_> = "abcYe".chars().collect();
+///
+/// let merged: Vec<_> = "aXcYe".chars().collect();
+/// assert_eq!(merge3(&base, &left, &right), vec![MergeChunk::Merged(merged)]);
+///
+/// let right: Vec<_> = "aZcde".chars().collect();
+/// assert_eq!(merge3(&base, &left, &right), vec![
+///     MergeChunk::Merged(vec!['a']),
+///     MergeChunk::Conflict { left: vec!['X'], right: vec!['Z'] },
+///     MergeChunk::Merged(vec!['c', 'd', 'e'])
+/// ]);
+/// ```
+pub fn merge3<T>(base: &[T], left: &[T], right: &[T]) -> Vec<MergeChunk<T>>
+        where T: Clone + Eq {
+    let left_matches = matches(base, left);
+    let right_matches = matches(base, right);
+
+    let mut chunks = Vec::new();
+    let (mut i, mut l, mut r) = (0, 0, 0);
+
+    loop {
+        let stable = (i..base.len()).find(|&k| {
+            left_matches[k].is_some() && right_matches[k].is_some()
+        });
+
+        let (end, end_l, end_r) = match stable {
+            Some(k) => (k, left_matches[k].unwrap(), right_matches[k].unwrap()),
+            None => (base.len(), left.len(), right.len())
+        };
+
+        push_unstable(&mut chunks, &base[i..end], &left[l..end_l], &right[r..end_r]);
+
+        match stable {
+            Some(k) => {
+                push_merged(&mut chunks, &base[k..k + 1]);
+                i = k + 1;
+                l = end_l + 1;
+                r = end_r + 1;
+            },
+
+            None => return chunks
+        }
+    }
+}
+
+/// For each element of `base`, finds the index of the element in `other` it's kept as, if any.
+fn matches<T>(base: &[T], other: &[T]) -> Vec<Option<usize>> where T: Eq {
+    let mut matches = vec![None; base.len()];
+    for (i, j) in LcsTable::new(base, other).subsequence_indices() {
+        matches[i] = Some(j);
+    }
+
+    matches
+}
+
+fn push_unstable<T>(chunks: &mut Vec<MergeChunk<T>>, base: &[T], left: &[T], right: &[T])
+        where T: Clone + Eq {
+    if left == base {
+        push_merged(chunks, right);
+    } else if right == base || left == right {
+        push_merged(chunks, left);
+    } else {
+        chunks.push(MergeChunk::Conflict { left: left.to_vec(), right: right.to_vec() });
+    }
+}
+
+fn push_merged<T>(chunks: &mut Vec<MergeChunk<T>>, elems: &[T]) where T: Clone {
+    if elems.is_empty() {
+        return;
+    }
+
+    if let Some(&mut MergeChunk::Merged(ref mut merged)) = chunks.last_mut() {
+        merged.extend_from_slice(elems);
+        return;
+    }
+
+    chunks.push(MergeChunk::Merged(elems.to_vec()));
+}
+
+#[test]
+fn test_merge3_clean() {
+    let base: Vec<_> = "abcdef".chars().collect();
+    let left: Vec<_> = "abXcdef".chars().collect();
+    let right: Vec<_> = "abcdf".chars().collect();
+
+    let merged: Vec<_> = "abXcdf".chars().collect();
+    assert_eq!(merge3(&base, &left, &right), vec![MergeChunk::Merged(merged)]);
+    assert_eq!(merge3(&base, &base, &base), vec![MergeChunk::Merged(base.clone())]);
+}
+
+#[test]
+fn test_merge3_same_change() {
+    let base: Vec<_> = "abc".chars().collect();
+    let both: Vec<_> = "aXc".chars().collect();
+
+    assert_eq!(merge3(&base, &both, &both), vec![MergeChunk::Merged(both.clone())]);
+}
+
+#[test]
+fn test_merge3_conflicting_insertions() {
+    let base: Vec<_> = "ab".chars().collect();
+    let left: Vec<_> = "aXb".chars().collect();
+    let right: Vec<_> = "aYb".chars().collect();
+
+    assert_eq!(merge3(&base, &left, &right), vec![
+        MergeChunk::Merged(vec!['a']),
+        MergeChunk::Conflict { left: vec!['X'], right: vec!['Y'] },
+        MergeChunk::Merged(vec!['b'])
+    ]);
+
+    let empty: Vec<char> = vec![];
+    assert_eq!(merge3(&empty, &left, &right), vec![
+        MergeChunk::Conflict { left: left.clone(), right: right.clone() }
+    ]);
+}
@@ -0,0 +1,279 @@
+//! Measuring how similar two sequences are without keeping an `LcsTable` around.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+use std::{cmp, mem};
+
+use hirschberg::lcs_row;
+
+/// Gets the length of the longest common subsequence between `a` and `b`, the same as
+/// `LcsTable::length`. Only one row of the table is kept in memory at a time, so this uses
+/// O(min(n, m)) space instead of O(n * m).
+///
+/// Example:
+///
+/// ```
+/// use lcs::lcs_length;
+///
+/// let a: Vec<_> = "gac".chars().collect();
+/// let b: Vec<_> = "agcat".chars().collect();
+///
+/// assert_eq!(2, lcs_length(&a, &b));
+/// ```
+pub fn lcs_length<T>(a: &[T], b: &[T]) -> usize where T: Eq {
+    let (longer, shorter) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    lcs_row(longer.iter(), shorter.iter())[shorter.len()]
+}
+
+/// Gets the length of the longest common subsequence between `a` and `b`, the same as
+/// `lcs_length`, using the bit-parallel algorithm of Hyyrö. Each row of the table is packed into
+/// bits, one per element of `b`, and updated with a few word operations per 64 elements. This
+/// takes O(n * m / 64) time, which is much faster than `lcs_length` for long sequences over a
+/// small alphabet, such as DNA.
+///
+/// This builds a bitset the size of `b` for each distinct element of `b`, so it suits inputs with
+/// few distinct elements.
+///
+/// This requires the `std` feature.
+///
+/// Example:
+///
+/// ```
+/// use lcs::lcs_length_bitparallel;
+///
+/// let a: Vec<_> = "gattaca".chars().collect();
+/// let b: Vec<_> = "tacgcat".chars().collect();
+///
+/// assert_eq!(4, lcs_length_bitparallel(&a, &b));
+/// ```
+#[cfg(feature = "std")]
+pub fn lcs_length_bitparallel<T>(a: &[T], b: &[T]) -> usize where T: Eq + Hash {
+    let words = (b.len() + 63) / 64;
+
+    // Which positions in `b` hold each element.
+    let mut matches: HashMap<&T, Vec<u64>> = HashMap::new();
+    for (j, elem) in b.iter().enumerate() {
+        matches.entry(elem).or_insert_with(|| vec![0; words])[j / 64] |= 1 << (j % 64);
+    }
+
+    // A zero bit in `row` marks a position in `b` where the length of the longest common
+    // subsequence goes up by one, compared to the position before it.
+    let mut row = vec![!0u64; words];
+    for elem in a {
+        let elem_matches = match matches.get(elem) {
+            Some(elem_matches) => elem_matches,
+            None => continue
+        };
+
+        let mut carry = false;
+        for (bits, &elem_bits) in row.iter_mut().zip(elem_matches) {
+            let matched = *bits & elem_bits;
+            let (sum, overflowed) = bits.overflowing_add(matched);
+            let (sum, carried) = sum.overflowing_add(carry as u64);
+
+            carry = overflowed || carried;
+            *bits = sum | (*bits - matched);
+        }
+    }
+
+    // Bits past the end of `b` don't count, so set them before counting zeros.
+    let used = b.len() % 64;
+    if used > 0 {
+        row[words - 1] |= !0 << used;
+    }
+
+    row.iter().map(|bits| bits.count_zeros() as usize).sum()
+}
+
+/// Finds which of `candidates` is most similar to `query`, by `LcsTable::ratio`. Returns the index
+/// of that candidate along with its ratio, or `None` if there are no candidates. If several are
+/// equally similar, the first of them wins.
+///
+/// A longest common subsequence can't be longer than the shorter of its two inputs, so candidates
+/// whose lengths alone rule out beating the best match so far are skipped without comparing them.
+///
+/// Example:
+///
+/// ```
+/// use lcs::best_match;
+///
+/// let query: Vec<_> = "color".chars().collect();
+/// let candidates: Vec<Vec<_>> = ["cooler", "colour", "collar"].iter()
+///     .map(|candidate| candidate.chars().collect())
+///     .collect();
+/// let candidates: Vec<&[char]> = candidates.iter().map(|candidate| &candidate[..]).collect();
+///
+/// let (index, ratio) = best_match(&query, &candidates).unwrap();
+/// assert_eq!(1, index);
+/// assert!((ratio - 10.0 / 11.0).abs() < 1e-9);
+/// ```
+pub fn best_match<T>(query: &[T], candidates: &[&[T]]) -> Option<(usize, f64)> where T: Eq {
+    let mut best: Option<(usize, f64)> = None;
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        let total = query.len() + candidate.len();
+
+        if let Some((_, best_ratio)) = best {
+            if ratio(cmp::min(query.len(), candidate.len()), total) <= best_ratio {
+                continue;
+            }
+        }
+
+        let candidate_ratio = ratio(lcs_length(query, candidate), total);
+        if best.map_or(true, |(_, best_ratio)| candidate_ratio > best_ratio) {
+            best = Some((index, candidate_ratio));
+        }
+    }
+
+    best
+}
+
+/// Twice `length` over `total`, the same as `LcsTable::ratio`.
+pub(crate) fn ratio(length: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 1.0;
+    }
+
+    2.0 * length as f64 / total as f64
+}
+
+/// Gets the number of single-element insertions and deletions needed to turn `a` into `b`, the
+/// same as `LcsTable::edit_distance`, but only if it's at most `max`. Otherwise, returns `None`.
+///
+/// Only the entries of the table within `max` of its diagonal can lead to a small enough distance,
+/// so this takes O((n + m) * max) time, and gives up as soon as no entry in a row is small enough.
+/// This makes it cheap to check many candidates against a tight bound, such as when suggesting
+/// spelling corrections.
+///
+/// Example:
+///
+/// ```
+/// use lcs::bounded_edit_distance;
+///
+/// let a: Vec<_> = "kitten".chars().collect();
+/// let b: Vec<_> = "sitting".chars().collect();
+///
+/// assert_eq!(Some(5), bounded_edit_distance(&a, &b, 5));
+/// assert_eq!(None, bounded_edit_distance(&a, &b, 4));
+/// ```
+pub fn bounded_edit_distance<T>(a: &[T], b: &[T], max: usize) -> Option<usize> where T: Eq {
+    if cmp::max(a.len(), b.len()) - cmp::min(a.len(), b.len()) > max {
+        return None;
+    }
+
+    // No distance is more than `a.len() + b.len()`, so a larger `max` changes nothing, and this
+    // keeps `max + 1` and `i + max` below from overflowing.
+    let max = cmp::min(max, a.len() + b.len());
+
+    // Anything above `max` is as good as infinite, so distances are capped at `max + 1`. Entries
+    // just outside the band are kept at that cap, so that the band can read them as neighbors.
+    let cap = max + 1;
+    let mut prev = vec![cap; b.len() + 1];
+    let mut row = vec![cap; b.len() + 1];
+
+    for (j, distance) in prev.iter_mut().enumerate().take(cmp::min(b.len(), max) + 1) {
+        *distance = j;
+    }
+
+    for i in 1..a.len() + 1 {
+        let lo = i.saturating_sub(max);
+        let hi = cmp::min(b.len(), i + max);
+
+        if lo == 0 {
+            row[0] = i;
+        } else {
+            row[lo - 1] = cap;
+        }
+
+        if hi < b.len() {
+            row[hi + 1] = cap;
+        }
+
+        for j in cmp::max(lo, 1)..hi + 1 {
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev[j - 1]
+            } else {
+                cmp::min(cap, 1 + cmp::min(prev[j], row[j - 1]))
+            };
+        }
+
+        // Distances never decrease along a path through the table, and every path to the end
+        // passes through this row.
+        if row[lo..hi + 1].iter().all(|&distance| distance > max) {
+            return None;
+        }
+
+        mem::swap(&mut prev, &mut row);
+    }
+
+    Some(prev[b.len()]).filter(|&distance| distance <= max)
+}
+
+#[test]
+fn test_lcs_length_matches_table() {
+    use {random_strings, LcsTable};
+
+    for (a, b) in random_strings(0xbf58_476d_1ce4_e5b9, 500) {
+        assert_eq!(LcsTable::new(&a, &b).length(), lcs_length(&a, &b));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lcs_length_bitparallel_matches_table() {
+    use {random_strings, LcsTable};
+
+    let mut cases = random_strings(0x9b05_688c_2b3e_6c1f, 300);
+
+    // Make sure some inputs span several words, with and without a partial last word.
+    for &(n, m) in &[(300, 128), (150, 200), (64, 65)] {
+        let a = (0..n).map(|i| (b'a' + (i * 7 % 5) as u8) as char).collect();
+        let b = (0..m).map(|i| (b'a' + (i * i % 3) as u8) as char).collect();
+        cases.push((a, b));
+    }
+
+    for (a, b) in cases {
+        assert_eq!(LcsTable::new(&a, &b).length(), lcs_length_bitparallel(&a, &b));
+    }
+}
+
+#[test]
+fn test_bounded_edit_distance_matches_table() {
+    use {random_strings, LcsTable};
+
+    for (a, b) in random_strings(0x9fb2_1c65_1e98_df25, 500) {
+        let distance = LcsTable::new(&a, &b).edit_distance();
+
+        for max in 0..distance + 3 {
+            let expected = if distance <= max { Some(distance) } else { None };
+            assert_eq!(expected, bounded_edit_distance(&a, &b, max));
+        }
+
+        assert_eq!(Some(distance), bounded_edit_distance(&a, &b, usize::MAX));
+    }
+}
+
+#[test]
+fn test_best_match() {
+    use {random_strings, LcsTable};
+    use prelude::*;
+
+    let empty: &[&[char]] = &[];
+    assert_eq!(None, best_match(&['a'], empty));
+
+    let cases = random_strings(0x27bb_2ee6_87b0_b0fd, 200);
+    for chunk in cases.chunks(10) {
+        let query = &chunk[0].0;
+        let candidates: Vec<&[char]> = chunk.iter().map(|(_, b)| &b[..]).collect();
+
+        let ratios: Vec<_> = candidates.iter()
+            .map(|candidate| LcsTable::new(query, candidate).ratio())
+            .collect();
+        let best = ratios.iter().cloned().fold(0.0, f64::max);
+        let index = ratios.iter().position(|&ratio| ratio == best).unwrap();
+
+        assert_eq!(Some((index, best)), best_match(query, &candidates));
+    }
+}
@@ -0,0 +1,164 @@
+//! Diffs that recognize blocks of elements that were moved, rather than deleted and reinserted.
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// A component of a diff from `LcsTable::diff_with_moves`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MoveAwareComponent<T> {
+    Insertion(T),
+    Unchanged(T, T),
+    Deletion(T),
+
+    /// A run of elements deleted from `a` at index `from` and inserted back into `b` at index
+    /// `to`. The elements in `content` are the ones from `a`.
+    Moved { from: usize, to: usize, content: Vec<T> }
+}
+
+/// A maximal run of insertions or deletions in a diff.
+struct Run {
+    /// Where the run starts in the diff.
+    position: usize,
+
+    /// Where the run starts in `a` (for deletions) or `b` (for insertions).
+    start: usize,
+
+    len: usize,
+
+    /// Where the run it was paired up with starts, if the two turned out to be a move.
+    counterpart: Option<usize>
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Computes a diff from `a` to `b` like `diff`, but with moved blocks flagged as such.
+    ///
+    /// Whenever a run of deletions is exactly equal to a run of insertions elsewhere in the diff,
+    /// the pair is reported as a single `MoveAwareComponent::Moved`, in the place of the deletions.
+    /// The insertions are left out; their position is the `to` field of the move. Runs are only
+    /// paired up when they are equal in their entirety, and each run is part of at most one move.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{LcsTable, MoveAwareComponent};
+    ///
+    /// let a: Vec<_> = "xyabc".chars().collect();
+    /// let b: Vec<_> = "abcxy".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.diff_with_moves(), vec![
+    ///     MoveAwareComponent::Moved { from: 0, to: 3, content: vec![&'x', &'y'] },
+    ///     MoveAwareComponent::Unchanged(&'a', &'a'),
+    ///     MoveAwareComponent::Unchanged(&'b', &'b'),
+    ///     MoveAwareComponent::Unchanged(&'c', &'c')
+    /// ]);
+    /// ```
+    pub fn diff_with_moves(&self) -> Vec<MoveAwareComponent<&T>> {
+        let diff = self.diff();
+        let (mut deletions, mut insertions) = runs(&diff);
+
+        for deletion in &mut deletions {
+            let insertion = insertions.iter_mut().find(|insertion| {
+                insertion.counterpart.is_none() && insertion.len == deletion.len &&
+                    (0..deletion.len).all(|k| {
+                        self.elems_eq(deletion.start + k, insertion.start + k)
+                    })
+            });
+
+            if let Some(insertion) = insertion {
+                insertion.counterpart = Some(deletion.start);
+                deletion.counterpart = Some(insertion.start);
+            }
+        }
+
+        let mut moved = vec![false; diff.len()];
+        for run in deletions.iter().chain(&insertions).filter(|run| run.counterpart.is_some()) {
+            for is_moved in &mut moved[run.position..run.position + run.len] {
+                *is_moved = true;
+            }
+        }
+
+        let mut deletions = deletions.into_iter()
+            .filter(|run| run.counterpart.is_some())
+            .peekable();
+        let mut moves_diff = Vec::with_capacity(diff.len());
+
+        for (position, component) in diff.into_iter().enumerate() {
+            if let Some(run) = deletions.next_if(|run| run.position == position) {
+                moves_diff.push(MoveAwareComponent::Moved {
+                    from: run.start,
+                    to: run.counterpart.unwrap(),
+                    content: self.a[run.start..run.start + run.len].iter().collect()
+                });
+            }
+
+            if moved[position] {
+                continue;
+            }
+
+            moves_diff.push(match component {
+                DiffComponent::Insertion(elem_b) => MoveAwareComponent::Insertion(elem_b),
+                DiffComponent::Unchanged(elem_a, elem_b) => {
+                    MoveAwareComponent::Unchanged(elem_a, elem_b)
+                },
+                DiffComponent::Deletion(elem_a) => MoveAwareComponent::Deletion(elem_a)
+            });
+        }
+
+        moves_diff
+    }
+}
+
+/// Finds the maximal runs of deletions and of insertions in `diff`, in order.
+fn runs<T>(diff: &[DiffComponent<T>]) -> (Vec<Run>, Vec<Run>) {
+    let mut deletions: Vec<Run> = Vec::new();
+    let mut insertions: Vec<Run> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    for (position, component) in diff.iter().enumerate() {
+        let (runs, start) = match *component {
+            DiffComponent::Insertion(_) => (&mut insertions, &mut j),
+            DiffComponent::Deletion(_) => (&mut deletions, &mut i),
+            DiffComponent::Unchanged(_, _) => {
+                i += 1;
+                j += 1;
+                continue;
+            }
+        };
+
+        match runs.last_mut() {
+            Some(run) if run.position + run.len == position => run.len += 1,
+            _ => runs.push(Run { position, start: *start, len: 1, counterpart: None })
+        }
+
+        *start += 1;
+    }
+
+    (deletions, insertions)
+}
+
+#[test]
+fn test_diff_with_moves() {
+    let a: Vec<_> = "abcdef".chars().collect();
+    let b: Vec<_> = "adefbc".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.diff_with_moves(), vec![
+        MoveAwareComponent::Unchanged(&'a', &'a'),
+        MoveAwareComponent::Moved { from: 1, to: 4, content: vec![&'b', &'c'] },
+        MoveAwareComponent::Unchanged(&'d', &'d'),
+        MoveAwareComponent::Unchanged(&'e', &'e'),
+        MoveAwareComponent::Unchanged(&'f', &'f')
+    ]);
+}
+
+#[test]
+fn test_diff_with_moves_requires_exact_runs() {
+    let a: Vec<_> = "xyab".chars().collect();
+    let b: Vec<_> = "abxz".chars().collect();
+
+    let table = LcsTable::new(&a, &b);
+    let diff = table.diff_with_moves();
+    assert!(diff.iter().all(|component| !matches!(*component, MoveAwareComponent::Moved { .. })));
+    assert_eq!(diff.len(), table.diff().len());
+}
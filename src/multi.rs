@@ -0,0 +1,126 @@
+//! Longest common subsequences of more than two sequences.
+
+use std::cmp;
+
+use prelude::*;
+
+/// Gets a longest common subsequence of three sequences `a`, `b`, and `c`. Each element of the
+/// result is a triple of matching elements from `a`, `b`, and `c`, in that order.
+///
+/// This builds a three-dimensional table, so it takes O(n * m * k) time and space for inputs of
+/// lengths *n*, *m*, and *k*. That adds up quickly: three sequences of a thousand elements each
+/// need a billion entries.
+///
+/// # Example
+///
+/// ```
+/// use lcs::lcs3;
+///
+/// let a: Vec<_> = "gattaca".chars().collect();
+/// let b: Vec<_> = "gtaaca".chars().collect();
+/// let c: Vec<_> = "tacat".chars().collect();
+///
+/// let lcs: String = lcs3(&a, &b, &c).into_iter().map(|(elem, _, _)| *elem).collect();
+/// assert_eq!("taca", lcs);
+/// ```
+pub fn lcs3<'a, T>(a: &'a [T], b: &'a [T], c: &'a [T]) -> Vec<(&'a T, &'a T, &'a T)>
+        where T: Eq {
+    let (n, m, k) = (a.len(), b.len(), c.len());
+
+    // `lengths[index(i, j, l)]` is the length of the LCS of `a[..i]`, `b[..j]`, and `c[..l]`.
+    let index = |i: usize, j: usize, l: usize| (i * (m + 1) + j) * (k + 1) + l;
+    let mut lengths = vec![0; (n + 1) * (m + 1) * (k + 1)];
+
+    for i in 1..n + 1 {
+        for j in 1..m + 1 {
+            for l in 1..k + 1 {
+                lengths[index(i, j, l)] = if a[i - 1] == b[j - 1] && b[j - 1] == c[l - 1] {
+                    lengths[index(i - 1, j - 1, l - 1)] + 1
+                } else {
+                    cmp::max(lengths[index(i - 1, j, l)],
+                             cmp::max(lengths[index(i, j - 1, l)], lengths[index(i, j, l - 1)]))
+                };
+            }
+        }
+    }
+
+    let mut lcs = Vec::with_capacity(lengths[index(n, m, k)]);
+    let (mut i, mut j, mut l) = (n, m, k);
+
+    while i > 0 && j > 0 && l > 0 {
+        let length = lengths[index(i, j, l)];
+
+        if a[i - 1] == b[j - 1] && b[j - 1] == c[l - 1] {
+            lcs.push((&a[i - 1], &b[j - 1], &c[l - 1]));
+            i -= 1;
+            j -= 1;
+            l -= 1;
+        } else if lengths[index(i - 1, j, l)] == length {
+            i -= 1;
+        } else if lengths[index(i, j - 1, l)] == length {
+            j -= 1;
+        } else {
+            l -= 1;
+        }
+    }
+
+    lcs.reverse();
+    lcs
+}
+
+#[test]
+fn test_lcs3() {
+    use {random_strings, LcsTable};
+
+    fn is_subsequence(sub: &[char], seq: &[char]) -> bool {
+        let mut seq = seq.iter();
+        sub.iter().all(|elem| seq.any(|other| other == elem))
+    }
+
+    // The length of the longest subsequence of `a` that's also a subsequence of `b` and `c`, by
+    // trying every subsequence of `a`.
+    fn brute_force(a: &[char], b: &[char], c: &[char]) -> usize {
+        (0u32..1 << a.len())
+            .map(|mask| -> Vec<char> {
+                a.iter()
+                    .enumerate()
+                    .filter(|&(k, _)| mask & (1 << k) != 0)
+                    .map(|(_, &elem)| elem)
+                    .collect()
+            })
+            .filter(|sub| is_subsequence(sub, b) && is_subsequence(sub, c))
+            .map(|sub| sub.len())
+            .max()
+            .unwrap()
+    }
+
+    let strings = random_strings(0x2545_f491_4f6c_dd1d, 300);
+
+    for window in strings.windows(2) {
+        let (ref a, ref b) = window[0];
+        let (ref c, _) = window[1];
+
+        let lcs = lcs3(a, b, c);
+        for &(elem_a, elem_b, elem_c) in &lcs {
+            assert!(elem_a == elem_b && elem_b == elem_c);
+        }
+
+        // The result has to be a subsequence of each input, so it can't be longer than any
+        // pairwise LCS.
+        let positions = |seq: &[char], pick: &dyn Fn(&(&char, &char, &char)) -> *const char| {
+            let mut seq_positions = seq.iter().map(|elem| elem as *const char);
+            lcs.iter().all(|triple| seq_positions.any(|ptr| ptr == pick(triple)))
+        };
+        assert!(positions(a, &|triple| triple.0));
+        assert!(positions(b, &|triple| triple.1));
+        assert!(positions(c, &|triple| triple.2));
+        assert!(lcs.len() <= LcsTable::new(a, b).length());
+        assert!(lcs.len() <= LcsTable::new(b, c).length());
+        if a.len() <= 12 {
+            assert_eq!(lcs.len(), brute_force(a, b, c));
+        }
+
+        // With two of the inputs the same, this is just an LCS of two sequences.
+        assert_eq!(LcsTable::new(a, b).length(), lcs3(a, b, b).len());
+    }
+}
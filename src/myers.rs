@@ -0,0 +1,167 @@
+//! Myers' [O(ND) difference algorithm][paper], which avoids building an `LcsTable` altogether.
+//!
+//! [paper]: http://www.xmailserver.org/diff2.pdf
+
+use DiffComponent;
+use prelude::*;
+
+/// Computes a diff from `a` to `b` using Myers' greedy algorithm. This takes O((n + m) * d) time
+/// and O(d^2) space, where *d* is the number of insertions and deletions in the diff, so it's
+/// much cheaper than `LcsTable::diff` for large, similar inputs.
+///
+/// The result has as few insertions and deletions as `LcsTable::diff` would, but when there are
+/// several equally small diffs, the two may pick different ones.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{myers_diff, DiffComponent};
+///
+/// let a: Vec<_> = "axb".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// assert_eq!(myers_diff(&a, &b), vec![
+///     DiffComponent::Unchanged(&'a', &'a'),
+///     DiffComponent::Deletion(&'x'),
+///     DiffComponent::Unchanged(&'b', &'b'),
+///     DiffComponent::Insertion(&'c')
+/// ]);
+/// ```
+pub fn myers_diff<'a, T>(a: &'a [T], b: &'a [T]) -> Vec<DiffComponent<&'a T>> where T: Eq {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = n + m;
+
+    // `v[offset + k]` is the furthest `x` reached so far on diagonal `k = x - y`. Before each
+    // round `d`, we save the diagonals the next round can read, so the path can be recovered.
+    let offset = max + 1;
+    let mut v = vec![0; 2 * max as usize + 3];
+    let mut trace = Vec::new();
+
+    'search: for d in 0..max + 1 {
+        trace.push(v[(offset - d - 1) as usize..(offset + d + 2) as usize].to_vec());
+
+        for k in (-d..d + 1).step_by(2) {
+            let mut x = if k == -d || (k != d && v[(offset + k - 1) as usize] <
+                                                 v[(offset + k + 1) as usize]) {
+                v[(offset + k + 1) as usize]
+            } else {
+                v[(offset + k - 1) as usize] + 1
+            };
+
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            v[(offset + k) as usize] = x;
+
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+
+        // The saved diagonals for round `d` range from `-d - 1` to `d + 1`.
+        let furthest = |k: isize| v[(k + d + 1) as usize];
+
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let prev_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+                k + 1
+            } else {
+                k - 1
+            };
+
+            (furthest(prev_k), furthest(prev_k) - prev_k)
+        };
+
+        while x > prev_x && y > prev_y {
+            diff.push(DiffComponent::Unchanged(&a[x as usize - 1], &b[y as usize - 1]));
+            x -= 1;
+            y -= 1;
+        }
+
+        if d > 0 {
+            if x == prev_x {
+                diff.push(DiffComponent::Insertion(&b[y as usize - 1]));
+            } else {
+                diff.push(DiffComponent::Deletion(&a[x as usize - 1]));
+            }
+        }
+
+        x = prev_x;
+        y = prev_y;
+    }
+
+    diff.reverse();
+    diff
+}
+
+#[test]
+fn test_myers_identical() {
+    let a: Vec<_> = "abc".chars().collect();
+
+    assert_eq!(myers_diff(&a, &a), vec![
+        DiffComponent::Unchanged(&'a', &'a'),
+        DiffComponent::Unchanged(&'b', &'b'),
+        DiffComponent::Unchanged(&'c', &'c')
+    ]);
+
+    let empty: Vec<char> = vec![];
+    assert!(myers_diff(&empty, &empty).is_empty());
+}
+
+#[test]
+fn test_myers_disjoint() {
+    let a: Vec<_> = "ab".chars().collect();
+    let b: Vec<_> = "xy".chars().collect();
+
+    assert_eq!(myers_diff(&a, &b), vec![
+        DiffComponent::Deletion(&'a'),
+        DiffComponent::Deletion(&'b'),
+        DiffComponent::Insertion(&'x'),
+        DiffComponent::Insertion(&'y')
+    ]);
+}
+
+#[test]
+fn test_myers_matches_table() {
+    use {random_strings, LcsTable};
+
+    for (a, b) in random_strings(0x9e37_79b9_7f4a_7c15, 500) {
+        let diff = myers_diff(&a, &b);
+        let table = LcsTable::new(&a, &b);
+
+        let edits = diff.iter()
+            .filter(|component| !matches!(**component, DiffComponent::Unchanged(_, _)))
+            .count();
+        assert_eq!(table.edit_distance(), edits);
+
+        let mut reconstructed_a = Vec::new();
+        let mut reconstructed_b = Vec::new();
+        for component in diff {
+            match component {
+                DiffComponent::Insertion(elem_b) => reconstructed_b.push(*elem_b),
+                DiffComponent::Unchanged(elem_a, elem_b) => {
+                    assert_eq!(elem_a, elem_b);
+                    reconstructed_a.push(*elem_a);
+                    reconstructed_b.push(*elem_b);
+                },
+                DiffComponent::Deletion(elem_a) => reconstructed_a.push(*elem_a)
+            }
+        }
+
+        assert_eq!(a, reconstructed_a);
+        assert_eq!(b, reconstructed_b);
+    }
+}
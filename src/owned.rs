@@ -0,0 +1,93 @@
+//! An `LcsTable` that owns its inputs, for when they don't already live in slices.
+
+use {fill, Cow, LcsTable};
+use prelude::*;
+
+/// An `LcsTable` that owns its inputs, along with the table built from them. The results of all of
+/// `LcsTable`'s methods are available through `table`, which borrows from this instead of from
+/// slices the caller has to keep around. See `LcsTable::from_iters`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedLcsTable<T> {
+    lengths: Vec<usize>,
+
+    a: Vec<T>,
+    b: Vec<T>
+}
+
+impl<T> OwnedLcsTable<T> where T: Eq {
+    /// Constructs an OwnedLcsTable for matching between two sequences `a` and `b`, taking
+    /// ownership of both.
+    pub fn new(a: Vec<T>, b: Vec<T>) -> OwnedLcsTable<T> {
+        let lengths = fill(a.len(), b.len(), |i, j| a[i] == b[j]);
+        OwnedLcsTable { lengths, a, b }
+    }
+
+    /// Gets an `LcsTable` for this table's inputs. This only borrows the table built in `new`, so
+    /// it's cheap to call as often as needed.
+    pub fn table(&self) -> LcsTable<'_, T> {
+        LcsTable { lengths: Cow::Borrowed(&self.lengths), a: &self.a, b: &self.b, eq: PartialEq::eq }
+    }
+
+    /// Gets the first of the two sequences.
+    pub fn a(&self) -> &[T] {
+        &self.a
+    }
+
+    /// Gets the second of the two sequences.
+    pub fn b(&self) -> &[T] {
+        &self.b
+    }
+
+    /// Takes back ownership of the two sequences, `a` and `b` in that order.
+    pub fn into_inner(self) -> (Vec<T>, Vec<T>) {
+        (self.a, self.b)
+    }
+}
+
+impl<'a, T> LcsTable<'a, T> {
+    /// Constructs a table for matching between the elements of `a` and `b`, collecting both into
+    /// vectors that the table owns. This saves keeping the inputs in slices that outlive the
+    /// table, which is awkward when they come from iterators.
+    ///
+    /// The result is an `OwnedLcsTable`, which gives out an `LcsTable` borrowing from it with
+    /// `OwnedLcsTable::table`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable};
+    ///
+    /// let owned = LcsTable::from_iters("gac".chars(), "agcat".chars());
+    /// assert_eq!(2, owned.table().length());
+    /// assert_eq!(owned.table().diff()[0], DiffComponent::Insertion(&'a'));
+    /// ```
+    pub fn from_iters<I, J>(a: I, b: J) -> OwnedLcsTable<T>
+            where T: Eq, I: IntoIterator<Item = T>, J: IntoIterator<Item = T> {
+        OwnedLcsTable::new(a.into_iter().collect(), b.into_iter().collect())
+    }
+}
+
+#[test]
+fn test_owned() {
+    use DiffComponent;
+
+    let owned = LcsTable::from_iters("gac".chars(), "agcat".chars());
+    assert_eq!(owned.a(), &['g', 'a', 'c']);
+    assert_eq!(owned.b(), &['a', 'g', 'c', 'a', 't']);
+
+    let table = owned.table();
+    assert_eq!(2, table.length());
+    assert_eq!(table.diff(), vec![
+        DiffComponent::Insertion(&'a'),
+        DiffComponent::Unchanged(&'g', &'g'),
+        DiffComponent::Insertion(&'c'),
+        DiffComponent::Unchanged(&'a', &'a'),
+        DiffComponent::Insertion(&'t'),
+        DiffComponent::Deletion(&'c')
+    ]);
+    assert_eq!(owned.into_inner(), ("gac".chars().collect(), "agcat".chars().collect()));
+
+    let empty = LcsTable::from_iters(Vec::<char>::new(), vec![]);
+    assert_eq!(0, empty.table().length());
+    assert!(empty.table().diff().is_empty());
+}
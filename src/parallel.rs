@@ -0,0 +1,117 @@
+//! Filling an `LcsTable` across several threads with [rayon][rayon].
+//!
+//! [rayon]: https://crates.io/crates/rayon
+
+use std::borrow::Cow;
+use std::cmp;
+
+use rayon::prelude::*;
+
+use LcsTable;
+use prelude::*;
+
+/// The side length of the square blocks the table is split into. Each block is filled by a single
+/// thread, so this trades off scheduling overhead against the amount of available parallelism.
+const BLOCK_SIZE: usize = 256;
+
+impl<'a, T> LcsTable<'a, T> where T: Eq + Sync {
+    /// Constructs a LcsTable for matching between two sequences `a` and `b`, filling the table
+    /// across rayon's thread pool. The result is identical to `LcsTable::new`.
+    ///
+    /// Each entry of the table depends on the entries above and to the left of it, so the table is
+    /// split into blocks which are filled one anti-diagonal of blocks at a time. For small inputs,
+    /// the coordination costs more than it saves, and `LcsTable::new` is faster.
+    ///
+    /// This requires the `rayon` feature.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let table = LcsTable::new_parallel(&a, &b);
+    /// assert_eq!(2, table.length());
+    /// ```
+    pub fn new_parallel(a: &'a [T], b: &'a [T]) -> LcsTable<'a, T> {
+        let width = b.len() + 1;
+        let mut lengths = vec![0; (a.len() + 1) * width];
+
+        {
+            let mut bands: Vec<_> = lengths[width..].chunks_mut(BLOCK_SIZE * width).collect();
+            let column_blocks = (b.len() + BLOCK_SIZE - 1) / BLOCK_SIZE;
+
+            // A band can't read the last row of the band above it while that one is being written
+            // to, so each band gets its own copy of that row, updated between anti-diagonals.
+            let mut tops = vec![vec![0; width]; bands.len()];
+
+            for diagonal in 0..bands.len() + column_blocks {
+                let blocks = |band: usize| {
+                    diagonal.checked_sub(band).filter(|&block| block < column_blocks)
+                };
+
+                bands.par_iter_mut().zip(tops.par_iter()).enumerate()
+                    .for_each(|(band_index, (band, top))| {
+                        if let Some(block) = blocks(band_index) {
+                            let columns = block * BLOCK_SIZE + 1..
+                                cmp::min((block + 1) * BLOCK_SIZE, b.len()) + 1;
+                            fill_block(&a[band_index * BLOCK_SIZE..], b, band, top, columns);
+                        }
+                    });
+
+                for band_index in 1..bands.len() {
+                    if let Some(block) = blocks(band_index - 1) {
+                        let columns = block * BLOCK_SIZE + 1..
+                            cmp::min((block + 1) * BLOCK_SIZE, b.len()) + 1;
+                        let above = &bands[band_index - 1];
+                        let last_row = &above[above.len() - width..];
+                        tops[band_index][columns.clone()].copy_from_slice(&last_row[columns]);
+                    }
+                }
+            }
+        }
+
+        LcsTable { lengths: Cow::Owned(lengths), a, b, eq: PartialEq::eq }
+    }
+}
+
+/// Fills `columns` of the rows in `band`, which correspond to the first few elements of `a`, one
+/// for each row. `top` is the row just above the band.
+fn fill_block<T>(a: &[T], b: &[T], band: &mut [usize], top: &[usize],
+                 columns: ::std::ops::Range<usize>) where T: Eq {
+    let width = top.len();
+
+    for (row, elem_a) in a.iter().enumerate().take(band.len() / width) {
+        let (above, rest) = band.split_at_mut(row * width);
+        let above = if row == 0 { top } else { &above[above.len() - width..] };
+        let current = &mut rest[..width];
+
+        for j in columns.clone() {
+            current[j] = if *elem_a == b[j - 1] {
+                1 + above[j - 1]
+            } else {
+                cmp::max(current[j - 1], above[j])
+            }
+        }
+    }
+}
+
+#[test]
+fn test_new_parallel_matches_new() {
+    use random_strings;
+
+    let mut cases = random_strings(0x8cb9_2ba7_2f3d_8dd7, 200);
+
+    // Make sure some inputs span several blocks in each direction.
+    for &(n, m) in &[(600, 300), (257, 1000), (BLOCK_SIZE, BLOCK_SIZE)] {
+        let a = (0..n).map(|i| (b'a' + (i * 7 % 5) as u8) as char).collect();
+        let b = (0..m).map(|i| (b'a' + (i * 3 % 4) as u8) as char).collect();
+        cases.push((a, b));
+    }
+
+    for (a, b) in cases {
+        assert_eq!(LcsTable::new(&a, &b).lengths, LcsTable::new_parallel(&a, &b).lengths);
+    }
+}
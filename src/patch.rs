@@ -0,0 +1,202 @@
+//! Applying a diff to a sequence, to get back the sequence it was diffed against, and describing
+//! a diff as a series of edits to apply.
+
+use std::borrow::Borrow;
+#[cfg(feature = "std")]
+use std::error::Error;
+use std::fmt;
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// The ways applying a diff to a sequence can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchError {
+    /// The diff expected to delete or keep an element at `index`, but the sequence had a different
+    /// element there, or had already ended.
+    Mismatch { index: usize },
+
+    /// The diff ended, but the sequence still had elements left, starting at `index`.
+    Incomplete { index: usize },
+
+    /// A copy starting at `index` reaches past the end of the sequence it copies from.
+    OutOfBounds { index: usize }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PatchError::Mismatch { index } => {
+                write!(f, "diff does not match the sequence at index {}", index)
+            },
+
+            PatchError::Incomplete { index } => {
+                write!(f, "diff ended before the sequence, at index {}", index)
+            },
+
+            PatchError::OutOfBounds { index } => {
+                write!(f, "copy starting at index {} reaches past the end of the sequence", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PatchError {}
+
+/// Applies `diff` to `a`, returning the sequence the diff leads to. Every `Unchanged` and
+/// `Deletion` component must match the next element of `a`, and the diff must account for all of
+/// `a`; otherwise, this returns an error saying where in `a` the patch stopped applying.
+///
+/// The diff's elements can be owned or borrowed, so a diff straight from `LcsTable::diff` works.
+///
+/// # Example
+///
+/// ```
+/// use lcs::{apply, LcsTable, PatchError};
+///
+/// let a: Vec<_> = "axb".chars().collect();
+/// let b: Vec<_> = "abc".chars().collect();
+///
+/// let table = LcsTable::new(&a, &b);
+/// let diff = table.diff();
+/// assert_eq!(apply(&a, &diff), Ok(b.clone()));
+///
+/// let c: Vec<_> = "ayb".chars().collect();
+/// assert_eq!(apply(&c, &diff), Err(PatchError::Mismatch { index: 1 }));
+/// ```
+pub fn apply<T, U>(a: &[T], diff: &[DiffComponent<U>]) -> Result<Vec<T>, PatchError>
+        where T: Clone + Eq, U: Borrow<T> {
+    let mut b = Vec::with_capacity(a.len());
+    let mut i = 0;
+
+    for component in diff {
+        match *component {
+            DiffComponent::Insertion(ref elem_b) => b.push(elem_b.borrow().clone()),
+
+            DiffComponent::Unchanged(ref elem_a, ref elem_b) => {
+                if a.get(i) != Some(elem_a.borrow()) {
+                    return Err(PatchError::Mismatch { index: i });
+                }
+
+                b.push(elem_b.borrow().clone());
+                i += 1;
+            },
+
+            DiffComponent::Deletion(ref elem_a) => {
+                if a.get(i) != Some(elem_a.borrow()) {
+                    return Err(PatchError::Mismatch { index: i });
+                }
+
+                i += 1;
+            }
+        }
+    }
+
+    if i != a.len() {
+        return Err(PatchError::Incomplete { index: i });
+    }
+
+    Ok(b)
+}
+
+/// A single edit in a shortest edit script, from `LcsTable::edit_script`. Positions refer to the
+/// sequence as it is just before the edit is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// Remove the element at position `at`.
+    Delete { at: usize },
+
+    /// Insert `b[elem_index]` at position `at`, shifting the elements after it over by one.
+    Insert { at: usize, elem_index: usize }
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Computes the shortest sequence of single-element edits that turns `a` into `b`. Applying the
+    /// edits one after the other, starting from `a`, gives `b`.
+    ///
+    /// The edits come in the same order as the components of `diff`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{EditOp, LcsTable};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.edit_script(), vec![
+    ///     EditOp::Delete { at: 1 },
+    ///     EditOp::Insert { at: 2, elem_index: 2 }
+    /// ]);
+    /// ```
+    pub fn edit_script(&self) -> Vec<EditOp> {
+        let mut script = Vec::with_capacity(self.edit_distance());
+        let (mut at, mut j) = (0, 0);
+
+        for component in self.diff_iter() {
+            match component {
+                DiffComponent::Insertion(_) => {
+                    script.push(EditOp::Insert { at, elem_index: j });
+                    at += 1;
+                    j += 1;
+                },
+
+                DiffComponent::Unchanged(_, _) => {
+                    at += 1;
+                    j += 1;
+                },
+
+                DiffComponent::Deletion(_) => script.push(EditOp::Delete { at })
+            }
+        }
+
+        script
+    }
+}
+
+#[test]
+fn test_apply_round_trip() {
+    use {random_strings, LcsTable};
+
+    for (a, b) in random_strings(0x6a09_e667_f3bc_c908, 500) {
+        let table = LcsTable::new(&a, &b);
+        assert_eq!(apply(&a, &table.diff()), Ok(b.clone()));
+    }
+}
+
+#[test]
+fn test_apply_errors() {
+    use DiffComponent::*;
+
+    let a: Vec<_> = "abc".chars().collect();
+
+    assert_eq!(apply(&a, &[Unchanged('a', 'a'), Deletion('x')]),
+               Err(PatchError::Mismatch { index: 1 }));
+    assert_eq!(apply(&a, &[Unchanged('a', 'a'), Deletion('b'), Deletion('c'), Deletion('d')]),
+               Err(PatchError::Mismatch { index: 3 }));
+    assert_eq!(apply(&a, &[Unchanged('a', 'a'), Insertion('z')]),
+               Err(PatchError::Incomplete { index: 1 }));
+}
+
+#[test]
+fn test_edit_script() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x4cf5_ad43_2745_937f, 500) {
+        let table = LcsTable::new(&a, &b);
+        let script = table.edit_script();
+        assert_eq!(table.edit_distance(), script.len());
+
+        let mut edited = a.clone();
+        for op in script {
+            match op {
+                EditOp::Delete { at } => { edited.remove(at); },
+                EditOp::Insert { at, elem_index } => edited.insert(at, b[elem_index])
+            }
+        }
+
+        assert_eq!(b, edited);
+    }
+}
@@ -0,0 +1,179 @@
+//! Longest common substrings against a fixed sequence, for answering many queries.
+
+use std::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use prelude::*;
+
+/// An index over a sequence `a` for finding the longest contiguous run of elements it has in
+/// common with other sequences. Unlike a subsequence, a substring can't skip over elements.
+///
+/// The index is a suffix automaton of `a`, which takes `O(a.len() * log k)` time to build for an
+/// alphabet of `k` distinct elements, and then answers each query in `O(b.len() * log k)` time,
+/// no matter how long `a` is. Building one pays off when the same `a` is compared against many
+/// sequences, such as when looking for duplicated passages in a corpus.
+///
+/// Transitions between states are kept in `BTreeMap`s, so elements need to be `Ord`.
+///
+/// # Example
+///
+/// ```
+/// use lcs::SubstringIndex;
+///
+/// let a: Vec<_> = "the quick brown fox".chars().collect();
+/// let index = SubstringIndex::new(&a);
+///
+/// let b: Vec<_> = "a quick bird".chars().collect();
+/// assert_eq!(index.longest_common_substring(&b), 3..11);
+///
+/// let b: Vec<_> = "brown bear".chars().collect();
+/// assert_eq!(index.longest_common_substring(&b), 10..16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubstringIndex<'a, T: 'a> {
+    states: Vec<State<'a, T>>
+}
+
+/// A state of the automaton, which stands for a set of substrings of `a` that all end at the
+/// same positions.
+#[derive(Debug, Clone)]
+struct State<'a, T: 'a> {
+    // The length of the longest substring in this state.
+    len: usize,
+
+    // The state of the longest suffix of this state's substrings that ends at more positions, or
+    // `None` for the initial state.
+    link: Option<usize>,
+
+    // Where the substrings of this state first end in `a`, as an exclusive index.
+    end: usize,
+
+    next: BTreeMap<&'a T, usize>
+}
+
+impl<'a, T> SubstringIndex<'a, T> where T: Ord {
+    /// Builds an index over `a`.
+    pub fn new(a: &'a [T]) -> SubstringIndex<'a, T> {
+        let mut states = vec![State { len: 0, link: None, end: 0, next: BTreeMap::new() }];
+        let mut last = 0;
+
+        for (pos, elem) in a.iter().enumerate() {
+            let current = states.len();
+            states.push(State {
+                len: states[last].len + 1,
+                link: None,
+                end: pos + 1,
+                next: BTreeMap::new()
+            });
+
+            let mut p = Some(last);
+            while let Some(state) = p {
+                if states[state].next.contains_key(elem) {
+                    break;
+                }
+
+                states[state].next.insert(elem, current);
+                p = states[state].link;
+            }
+
+            states[current].link = Some(match p {
+                None => 0,
+                Some(p) => {
+                    let q = states[p].next[elem];
+                    if states[p].len + 1 == states[q].len {
+                        q
+                    } else {
+                        // `q` also holds longer substrings that don't end here, so split off the
+                        // shorter ones that do into a state of their own.
+                        let clone = states.len();
+                        let split = State {
+                            len: states[p].len + 1,
+                            link: states[q].link,
+                            end: states[q].end,
+                            next: states[q].next.clone()
+                        };
+                        states.push(split);
+
+                        let mut p = Some(p);
+                        while let Some(state) = p {
+                            if states[state].next.get(elem) != Some(&q) {
+                                break;
+                            }
+
+                            states[state].next.insert(elem, clone);
+                            p = states[state].link;
+                        }
+
+                        states[q].link = Some(clone);
+                        clone
+                    }
+                }
+            });
+
+            last = current;
+        }
+
+        SubstringIndex { states }
+    }
+
+    /// Finds the longest substring of `a` that's also a substring of `b`, as a range of indices
+    /// into `a`. If there are several, this is the first one found scanning through `b`, at its
+    /// first occurrence in `a`. If `a` and `b` have no elements in common, the range is empty.
+    pub fn longest_common_substring(&self, b: &[T]) -> Range<usize> {
+        let (mut state, mut len) = (0, 0);
+        let (mut best_len, mut best_end) = (0, 0);
+
+        for elem in b {
+            // Drop elements from the start of the current match until it can be extended.
+            while state != 0 && !self.states[state].next.contains_key(elem) {
+                state = self.states[state].link.unwrap_or(0);
+                len = self.states[state].len;
+            }
+
+            match self.states[state].next.get(elem) {
+                Some(&next) => {
+                    state = next;
+                    len += 1;
+                },
+                None => len = 0
+            }
+
+            if len > best_len {
+                best_len = len;
+                best_end = self.states[state].end;
+            }
+        }
+
+        best_end - best_len..best_end
+    }
+}
+
+#[test]
+fn test_longest_common_substring() {
+    use random_strings;
+
+    fn brute_force(a: &[char], b: &[char]) -> usize {
+        (0..a.len())
+            .flat_map(|i| (0..b.len()).map(move |j| (i, j)))
+            .map(|(i, j)| a[i..].iter().zip(&b[j..]).take_while(|&(x, y)| x == y).count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    for (a, b) in random_strings(0xc21e_5f94_07b3_a86d, 300) {
+        let range = SubstringIndex::new(&a).longest_common_substring(&b);
+
+        assert_eq!(range.len(), brute_force(&a, &b));
+        assert!(range.is_empty() ||
+                b.windows(range.len()).any(|window| *window == a[range.clone()]));
+    }
+
+    let a: Vec<_> = "aaaaaaaa".chars().collect();
+    let b: Vec<_> = "xaaay".chars().collect();
+    assert_eq!(SubstringIndex::new(&a).longest_common_substring(&b), 0..3);
+    assert_eq!(SubstringIndex::new(&a).longest_common_substring(&[]), 0..0);
+}
@@ -0,0 +1,78 @@
+//! Keeping the similarity measures of an `LcsTable` around after the table itself is gone.
+
+use metrics::ratio;
+use LcsTable;
+
+/// The lengths of two sequences and of their longest common subsequence, which is all it takes to
+/// get the measures of how similar they are. Unlike an `LcsTable`, this doesn't borrow the
+/// sequences, so it can be kept around in a cache while they change. See `LcsTable::into_summary`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LcsSummary {
+    pub len_a: usize,
+    pub len_b: usize,
+    pub lcs_len: usize
+}
+
+impl LcsSummary {
+    /// Gets the length of the longest common subsequence. See `LcsTable::length`.
+    pub fn length(&self) -> usize {
+        self.lcs_len
+    }
+
+    /// Gets the number of insertions and deletions needed to turn `a` into `b`. See
+    /// `LcsTable::edit_distance`.
+    pub fn edit_distance(&self) -> usize {
+        self.len_a + self.len_b - 2 * self.lcs_len
+    }
+
+    /// Gets how similar `a` and `b` are, as a number between 0.0 and 1.0. See `LcsTable::ratio`.
+    pub fn ratio(&self) -> f64 {
+        ratio(self.lcs_len, self.len_a + self.len_b)
+    }
+}
+
+impl<'a, T, E> LcsTable<'a, T, E> where E: Fn(&T, &T) -> bool {
+    /// Drops the table, keeping only what's needed for `length`, `edit_distance`, and `ratio`.
+    /// This ends the borrow of `a` and `b`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let mut a: Vec<_> = "gac".chars().collect();
+    /// let b: Vec<_> = "agcat".chars().collect();
+    ///
+    /// let summary = LcsTable::new(&a, &b).into_summary();
+    /// a.push('t');
+    ///
+    /// assert_eq!(2, summary.length());
+    /// assert_eq!(4, summary.edit_distance());
+    /// assert_eq!(0.5, summary.ratio());
+    /// ```
+    pub fn into_summary(self) -> LcsSummary {
+        LcsSummary { len_a: self.a.len(), len_b: self.b.len(), lcs_len: self.length() }
+    }
+}
+
+#[test]
+fn test_summary() {
+    use prelude::*;
+
+    let a: Vec<_> = "gac".chars().collect();
+    let b: Vec<_> = "agcat".chars().collect();
+
+    let summary = LcsTable::new(&a, &b).into_summary();
+    assert_eq!(LcsSummary { len_a: 3, len_b: 5, lcs_len: 2 }, summary);
+    assert_eq!(2, summary.length());
+    assert_eq!(4, summary.edit_distance());
+    assert_eq!(0.5, summary.ratio());
+
+    let empty: Vec<char> = vec![];
+    let summary = LcsTable::new(&empty, &empty).into_summary();
+    assert_eq!((0, 0, 1.0), (summary.length(), summary.edit_distance(), summary.ratio()));
+
+    let summary = LcsTable::new(&a, &empty).into_summary();
+    assert_eq!((0, 3, 0.0), (summary.length(), summary.edit_distance(), summary.ratio()));
+}
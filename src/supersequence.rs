@@ -0,0 +1,52 @@
+//! Shortest common supersequences, the counterpart to longest common subsequences.
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// Gets a shortest sequence that has both `a` and `b` as subsequences.
+///
+/// This is found by walking the diff from `a` to `b` and keeping every element of it once: the
+/// longest common subsequence is shared, and everything else is taken from whichever sequence it
+/// came from. So the result has `a.len() + b.len() - lcs_length(a, b)` elements.
+///
+/// # Example
+///
+/// ```
+/// use lcs::shortest_common_supersequence;
+///
+/// let a: Vec<_> = "abac".chars().collect();
+/// let b: Vec<_> = "cab".chars().collect();
+///
+/// let scs: String = shortest_common_supersequence(&a, &b).into_iter().collect();
+/// assert_eq!("cabac", scs);
+/// ```
+pub fn shortest_common_supersequence<T>(a: &[T], b: &[T]) -> Vec<T> where T: Clone + Eq {
+    LcsTable::new(a, b).diff_iter()
+        .map(|component| match component {
+            DiffComponent::Insertion(elem) |
+            DiffComponent::Unchanged(elem, _) |
+            DiffComponent::Deletion(elem) => elem.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn test_shortest_common_supersequence() {
+    use {lcs_length, random_strings};
+
+    fn is_subsequence(short: &[char], long: &[char]) -> bool {
+        let mut long = long.iter();
+        short.iter().all(|elem| long.any(|other| other == elem))
+    }
+
+    let a: Vec<_> = "AGGTAB".chars().collect();
+    let b: Vec<_> = "GXTXAYB".chars().collect();
+    assert_eq!(9, shortest_common_supersequence(&a, &b).len());
+
+    for (a, b) in random_strings(0xb8f2_07e4_63ad_1c95, 200) {
+        let scs = shortest_common_supersequence(&a, &b);
+
+        assert_eq!(scs.len(), a.len() + b.len() - lcs_length(&a, &b));
+        assert!(is_subsequence(&a, &scs) && is_subsequence(&b, &scs));
+    }
+}
@@ -0,0 +1,97 @@
+//! Classifying diff components by kind alone, for converting to other diff libraries' types.
+
+use DiffComponent;
+
+/// The kind of a `DiffComponent`, without its elements. This mirrors `similar::ChangeTag`, and
+/// converts to it with the `similar` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ChangeTag {
+    /// An element that's in both `a` and `b`.
+    Equal,
+
+    /// An element that's only in `a`.
+    Delete,
+
+    /// An element that's only in `b`.
+    Insert
+}
+
+impl<T> DiffComponent<T> {
+    /// Gets what kind of component this is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{ChangeTag, DiffComponent};
+    ///
+    /// assert_eq!(ChangeTag::Insert, DiffComponent::Insertion('x').tag());
+    /// assert_eq!(ChangeTag::Equal, DiffComponent::Unchanged('y', 'y').tag());
+    /// assert_eq!(ChangeTag::Delete, DiffComponent::Deletion('z').tag());
+    /// ```
+    pub fn tag(&self) -> ChangeTag {
+        match *self {
+            DiffComponent::Insertion(_) => ChangeTag::Insert,
+            DiffComponent::Unchanged(_, _) => ChangeTag::Equal,
+            DiffComponent::Deletion(_) => ChangeTag::Delete
+        }
+    }
+}
+
+/// Converts to the equivalent tag of the [similar][similar] crate.
+///
+/// This requires the `similar` feature.
+///
+/// [similar]: https://crates.io/crates/similar
+#[cfg(feature = "similar")]
+impl From<ChangeTag> for similar::ChangeTag {
+    fn from(tag: ChangeTag) -> similar::ChangeTag {
+        match tag {
+            ChangeTag::Equal => similar::ChangeTag::Equal,
+            ChangeTag::Delete => similar::ChangeTag::Delete,
+            ChangeTag::Insert => similar::ChangeTag::Insert
+        }
+    }
+}
+
+/// Converts from the equivalent tag of the [similar][similar] crate.
+///
+/// This requires the `similar` feature.
+///
+/// [similar]: https://crates.io/crates/similar
+#[cfg(feature = "similar")]
+impl From<similar::ChangeTag> for ChangeTag {
+    fn from(tag: similar::ChangeTag) -> ChangeTag {
+        match tag {
+            similar::ChangeTag::Equal => ChangeTag::Equal,
+            similar::ChangeTag::Delete => ChangeTag::Delete,
+            similar::ChangeTag::Insert => ChangeTag::Insert
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "similar")]
+fn test_similar_change_tags() {
+    use LcsTable;
+    use prelude::*;
+
+    let a = vec!["a", "b", "c"];
+    let b = vec!["a", "c", "d"];
+
+    let mut ours: Vec<similar::ChangeTag> = LcsTable::new(&a, &b).diff().iter()
+        .map(|component| component.tag().into())
+        .collect();
+    let mut theirs = Vec::new();
+    for op in similar::capture_diff_slices(similar::Algorithm::Lcs, &a, &b) {
+        theirs.extend(op.iter_changes(&a, &b).map(|change| change.tag()));
+    }
+
+    // Ties between insertions and deletions may be ordered differently, but the kinds line up.
+    ours.sort();
+    theirs.sort();
+    assert_eq!(ours, theirs);
+
+    for &tag in &[ChangeTag::Equal, ChangeTag::Delete, ChangeTag::Insert] {
+        assert_eq!(tag, ChangeTag::from(similar::ChangeTag::from(tag)));
+    }
+}
@@ -0,0 +1,503 @@
+//! Diffing strings by word or by line, rather than as slices of arbitrary elements.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+use std::ops::Range;
+
+#[cfg(feature = "unicode")]
+use unicode_segmentation::UnicodeSegmentation;
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// Computes a diff from `a` to `b` word by word.
+///
+/// Both strings are split into alternating runs of whitespace and non-whitespace, and the runs are
+/// diffed as elements. Whitespace is kept as tokens of its own, so concatenating the `Unchanged`
+/// and `Insertion` components reproduces `b` exactly, and the `Unchanged` and `Deletion`
+/// components reproduce `a`.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+/// use lcs::text::word_diff;
+///
+/// assert_eq!(word_diff("the quick fox", "the slow fox"), vec![
+///     DiffComponent::Unchanged("the".to_string(), "the".to_string()),
+///     DiffComponent::Unchanged(" ".to_string(), " ".to_string()),
+///     DiffComponent::Insertion("slow".to_string()),
+///     DiffComponent::Deletion("quick".to_string()),
+///     DiffComponent::Unchanged(" ".to_string(), " ".to_string()),
+///     DiffComponent::Unchanged("fox".to_string(), "fox".to_string())
+/// ]);
+/// ```
+pub fn word_diff(a: &str, b: &str) -> Vec<DiffComponent<String>> {
+    owned_diff(&words(a), &words(b))
+}
+
+/// Computes a diff from `a` to `b` character by character.
+///
+/// The characters are collected into temporary vectors which don't outlive this function, so the
+/// components hold copies of the characters, rather than references like `LcsTable::diff` gives.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+/// use lcs::text::str_diff;
+///
+/// assert_eq!(str_diff("cat", "cut"), vec![
+///     DiffComponent::Unchanged('c', 'c'),
+///     DiffComponent::Insertion('u'),
+///     DiffComponent::Deletion('a'),
+///     DiffComponent::Unchanged('t', 't')
+/// ]);
+/// ```
+pub fn str_diff(a: &str, b: &str) -> Vec<DiffComponent<char>> {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+
+    LcsTable::new(&a, &b).diff_cloned()
+}
+
+/// Computes a diff from `a` to `b` grapheme cluster by grapheme cluster, so that characters that
+/// display as one, such as a letter and its combining accent or an emoji and its modifiers, are
+/// never split up. Concatenating the `Unchanged` and `Insertion` components reproduces `b`
+/// exactly, and the `Unchanged` and `Deletion` components reproduce `a`.
+///
+/// This requires the `unicode` feature.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+/// use lcs::text::grapheme_diff;
+///
+/// // "e" followed by a combining acute accent, versus a plain "e".
+/// assert_eq!(grapheme_diff("ca\u{301}fe\u{301}", "cafe\u{301}"), vec![
+///     DiffComponent::Unchanged("c".to_string(), "c".to_string()),
+///     DiffComponent::Insertion("a".to_string()),
+///     DiffComponent::Deletion("a\u{301}".to_string()),
+///     DiffComponent::Unchanged("f".to_string(), "f".to_string()),
+///     DiffComponent::Unchanged("e\u{301}".to_string(), "e\u{301}".to_string())
+/// ]);
+/// ```
+#[cfg(feature = "unicode")]
+pub fn grapheme_diff(a: &str, b: &str) -> Vec<DiffComponent<String>> {
+    let graphemes_a: Vec<_> = a.graphemes(true).collect();
+    let graphemes_b: Vec<_> = b.graphemes(true).collect();
+
+    owned_diff(&graphemes_a, &graphemes_b)
+}
+
+/// How `line_diff_with` should treat the difference between `\r\n` and `\n` line endings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEndings {
+    /// A line ending in `\r\n` differs from the same line ending in `\n`.
+    Exact,
+
+    /// A line ending in `\r\n` is unchanged from the same line ending in `\n`. The `Unchanged`
+    /// components still hold each side's original line, endings included.
+    IgnoreCr
+}
+
+/// Computes a diff from `a` to `b` line by line. This is `line_diff_with` using
+/// `LineEndings::Exact`.
+///
+/// Each line keeps its `\n` (and `\r`, if any), so a last line without a trailing newline is
+/// different from the same line with one, and concatenating the `Unchanged` and `Insertion`
+/// components reproduces `b` exactly.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+/// use lcs::text::line_diff;
+///
+/// assert_eq!(line_diff("a\nb\n", "a\nb"), vec![
+///     DiffComponent::Unchanged("a\n".to_string(), "a\n".to_string()),
+///     DiffComponent::Insertion("b".to_string()),
+///     DiffComponent::Deletion("b\n".to_string())
+/// ]);
+/// ```
+pub fn line_diff(a: &str, b: &str) -> Vec<DiffComponent<String>> {
+    line_diff_with(a, b, LineEndings::Exact)
+}
+
+/// Computes a diff from `a` to `b` line by line, with `endings` deciding whether `\r\n` and `\n`
+/// line endings are considered different. See `line_diff`.
+///
+/// # Example
+///
+/// ```
+/// use lcs::DiffComponent;
+/// use lcs::text::{line_diff_with, LineEndings};
+///
+/// assert_eq!(line_diff_with("a\r\n", "a\n", LineEndings::IgnoreCr), vec![
+///     DiffComponent::Unchanged("a\r\n".to_string(), "a\n".to_string())
+/// ]);
+/// ```
+pub fn line_diff_with(a: &str, b: &str, endings: LineEndings) -> Vec<DiffComponent<String>> {
+    owned_diff(&lines(a, endings), &lines(b, endings))
+}
+
+/// Computes a diff from the lines of `a` to the lines of `b`, like `line_diff`, reading both from
+/// buffered sources such as files.
+///
+/// Each distinct line is interned as a number before diffing, so the table compares numbers rather
+/// than strings. Reading stops at the first IO error, which is returned; so are lines that aren't
+/// valid UTF-8.
+///
+/// This requires the `std` feature.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use lcs::DiffComponent;
+/// use lcs::text::line_diff_reader;
+///
+/// let a = Cursor::new("one\ntwo\n");
+/// let b = Cursor::new("one\n2\n");
+///
+/// assert_eq!(line_diff_reader(a, b).unwrap(), vec![
+///     DiffComponent::Unchanged("one\n".to_string(), "one\n".to_string()),
+///     DiffComponent::Insertion("2\n".to_string()),
+///     DiffComponent::Deletion("two\n".to_string())
+/// ]);
+/// ```
+#[cfg(feature = "std")]
+pub fn line_diff_reader<A, B>(a: A, b: B) -> io::Result<Vec<DiffComponent<String>>>
+        where A: BufRead, B: BufRead {
+    let lines_a = read_lines(a)?;
+    let lines_b = read_lines(b)?;
+
+    let (ids_a, ids_b) = {
+        let mut interned = HashMap::new();
+        (intern(&mut interned, &lines_a), intern(&mut interned, &lines_b))
+    };
+
+    let mut lines_a = lines_a.into_iter();
+    let mut lines_b = lines_b.into_iter();
+
+    Ok(LcsTable::new(&ids_a, &ids_b).diff_iter()
+        .map(|component| match component {
+            DiffComponent::Insertion(_) => DiffComponent::Insertion(lines_b.next().unwrap()),
+            DiffComponent::Unchanged(_, _) => {
+                DiffComponent::Unchanged(lines_a.next().unwrap(), lines_b.next().unwrap())
+            },
+            DiffComponent::Deletion(_) => DiffComponent::Deletion(lines_a.next().unwrap())
+        })
+        .collect())
+}
+
+/// Maps each line to a number, such that equal lines get equal numbers.
+#[cfg(feature = "std")]
+fn intern<'l>(interned: &mut HashMap<&'l str, usize>, lines: &'l [String]) -> Vec<usize> {
+    lines.iter()
+        .map(|line| {
+            let next = interned.len();
+            *interned.entry(&line[..]).or_insert(next)
+        })
+        .collect()
+}
+
+/// Reads all of the lines from `reader`, each with its line ending, if any.
+#[cfg(feature = "std")]
+fn read_lines<R>(mut reader: R) -> io::Result<Vec<String>> where R: BufRead {
+    let mut lines = Vec::new();
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(lines);
+        }
+
+        lines.push(line);
+    }
+}
+
+/// An edit to a string: replace the bytes in `range` with `new_text`. See `LcsTable::text_edits`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The byte offsets in the original string of the text to replace. An empty range inserts
+    /// `new_text` at that offset.
+    pub range: Range<usize>,
+
+    /// The text to put in place of `range`. An empty string deletes the range.
+    pub new_text: String
+}
+
+impl<'a, E> LcsTable<'a, char, E> where E: Fn(&char, &char) -> bool {
+    /// Describes the diff from `a` to `b` as edits to the string `a` came from, in the style of an
+    /// LSP `TextEdit`. Each run of consecutive insertions and deletions becomes a single edit, and
+    /// the edits are in order, with ranges as byte offsets into the original string.
+    ///
+    /// The ranges all refer to the original string, so applying the edits one at a time needs to
+    /// go from last to first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    /// use lcs::text::TextEdit;
+    ///
+    /// let a: Vec<_> = "héllo wörld".chars().collect();
+    /// let b: Vec<_> = "hello world".chars().collect();
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.text_edits(), vec![
+    ///     TextEdit { range: 1..3, new_text: "e".to_string() },
+    ///     TextEdit { range: 8..10, new_text: "o".to_string() }
+    /// ]);
+    /// ```
+    pub fn text_edits(&self) -> Vec<TextEdit> {
+        let mut edits: Vec<TextEdit> = Vec::new();
+        let mut offset = 0;
+        let mut in_run = false;
+
+        for component in self.diff_iter() {
+            if let DiffComponent::Unchanged(elem_a, _) = component {
+                offset += elem_a.len_utf8();
+                in_run = false;
+                continue;
+            }
+
+            if !in_run {
+                edits.push(TextEdit { range: offset..offset, new_text: String::new() });
+                in_run = true;
+            }
+
+            let edit = edits.last_mut().unwrap();
+            match component {
+                DiffComponent::Insertion(elem_b) => edit.new_text.push(*elem_b),
+                DiffComponent::Deletion(elem_a) => {
+                    offset += elem_a.len_utf8();
+                    edit.range.end = offset;
+                },
+                DiffComponent::Unchanged(_, _) => unreachable!()
+            }
+        }
+
+        edits
+    }
+}
+
+/// A line, compared according to some `LineEndings`.
+struct Line<'a> {
+    text: &'a str,
+    endings: LineEndings
+}
+
+impl<'a> Line<'a> {
+    fn content(&self) -> &'a str {
+        match self.endings {
+            LineEndings::Exact => self.text,
+            LineEndings::IgnoreCr => self.text.strip_suffix("\r\n")
+                .or_else(|| self.text.strip_suffix('\n'))
+                .unwrap_or(self.text)
+        }
+    }
+
+    fn has_newline(&self) -> bool {
+        self.text.ends_with('\n')
+    }
+}
+
+impl<'a> PartialEq for Line<'a> {
+    fn eq(&self, other: &Line<'a>) -> bool {
+        self.content() == other.content() && self.has_newline() == other.has_newline()
+    }
+}
+
+impl<'a> Eq for Line<'a> {}
+
+impl<'a> AsRef<str> for Line<'a> {
+    fn as_ref(&self) -> &str {
+        self.text
+    }
+}
+
+fn lines(s: &str, endings: LineEndings) -> Vec<Line<'_>> {
+    s.split_inclusive('\n').map(|text| Line { text, endings }).collect()
+}
+
+/// Splits `s` into maximal runs of whitespace and of non-whitespace characters.
+fn words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        let prev = s[..i].chars().next_back();
+        if prev.map_or(false, |prev| prev.is_whitespace() != c.is_whitespace()) {
+            words.push(&s[start..i]);
+            start = i;
+        }
+    }
+
+    if start < s.len() {
+        words.push(&s[start..]);
+    }
+
+    words
+}
+
+fn owned_diff<T>(a: &[T], b: &[T]) -> Vec<DiffComponent<String>> where T: Eq + AsRef<str> {
+    let owned = |elem: &T| String::from(elem.as_ref());
+
+    LcsTable::new(a, b).diff_iter()
+        .map(|component| match component {
+            DiffComponent::Insertion(elem_b) => DiffComponent::Insertion(owned(elem_b)),
+            DiffComponent::Unchanged(elem_a, elem_b) => {
+                DiffComponent::Unchanged(owned(elem_a), owned(elem_b))
+            },
+            DiffComponent::Deletion(elem_a) => DiffComponent::Deletion(owned(elem_a))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+fn reconstruct(diff: &[DiffComponent<String>]) -> (String, String) {
+    let mut a = String::new();
+    let mut b = String::new();
+
+    for component in diff {
+        match *component {
+            DiffComponent::Insertion(ref elem_b) => b.push_str(elem_b),
+            DiffComponent::Unchanged(ref elem_a, ref elem_b) => {
+                a.push_str(elem_a);
+                b.push_str(elem_b);
+            },
+            DiffComponent::Deletion(ref elem_a) => a.push_str(elem_a)
+        }
+    }
+
+    (a, b)
+}
+
+#[test]
+fn test_word_diff_round_trip() {
+    let cases = [
+        ("", ""),
+        ("one", ""),
+        ("  leading and trailing  ", "leading\tand\n\ntrailing"),
+        ("a b  c", "a  b c "),
+        ("héllo wörld", "hello wörld")
+    ];
+
+    for &(a, b) in &cases {
+        let diff = word_diff(a, b);
+        assert_eq!((String::from(a), String::from(b)), reconstruct(&diff));
+    }
+}
+
+#[test]
+fn test_str_diff() {
+    let (a, b) = ("héllo wörld", "hello world!");
+    let diff = str_diff(a, b);
+
+    let mut reconstructed = (String::new(), String::new());
+    for component in diff {
+        match component {
+            DiffComponent::Insertion(elem_b) => reconstructed.1.push(elem_b),
+            DiffComponent::Unchanged(elem_a, elem_b) => {
+                reconstructed.0.push(elem_a);
+                reconstructed.1.push(elem_b);
+            },
+            DiffComponent::Deletion(elem_a) => reconstructed.0.push(elem_a)
+        }
+    }
+
+    assert_eq!((String::from(a), String::from(b)), reconstructed);
+    assert!(str_diff("", "").is_empty());
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_grapheme_diff() {
+    let cases = [
+        ("", "\u{1f44d}\u{1f3fd}"),
+        ("\u{1f44d}\u{1f3fd} ok", "\u{1f44d}\u{1f3ff} ok"),
+        ("n\u{303}o", "no\u{303}")
+    ];
+
+    for &(a, b) in &cases {
+        let diff = grapheme_diff(a, b);
+        assert_eq!((String::from(a), String::from(b)), reconstruct(&diff));
+    }
+
+    // Changing a skin tone replaces the whole emoji, not just the modifier.
+    let diff = grapheme_diff("\u{1f44d}\u{1f3fd}", "\u{1f44d}\u{1f3ff}");
+    assert_eq!(diff, vec![
+        DiffComponent::Insertion(String::from("\u{1f44d}\u{1f3ff}")),
+        DiffComponent::Deletion(String::from("\u{1f44d}\u{1f3fd}"))
+    ]);
+}
+
+#[test]
+fn test_text_edits() {
+    let cases = [
+        ("", "abc"),
+        ("abc", ""),
+        ("héllo wörld", "hallo welt!"),
+        ("a\u{1f600}b", "ab\u{1f600}")
+    ];
+
+    for &(a, b) in &cases {
+        let chars_a: Vec<_> = a.chars().collect();
+        let chars_b: Vec<_> = b.chars().collect();
+
+        let mut edited = String::from(a);
+        for edit in LcsTable::new(&chars_a, &chars_b).text_edits().into_iter().rev() {
+            edited.replace_range(edit.range, &edit.new_text);
+        }
+
+        assert_eq!(b, edited);
+    }
+}
+
+#[test]
+fn test_line_diff() {
+    let cases = [
+        ("", "a"),
+        ("a\nb\nc\n", "a\nc\n\n"),
+        ("a\r\nb\r\n", "a\nb"),
+        ("\n\n", "\n")
+    ];
+
+    for &(a, b) in &cases {
+        for &endings in &[LineEndings::Exact, LineEndings::IgnoreCr] {
+            let diff = line_diff_with(a, b, endings);
+            assert_eq!((String::from(a), String::from(b)), reconstruct(&diff));
+        }
+    }
+
+    let changed = |diff: Vec<DiffComponent<String>>| {
+        diff.iter()
+            .filter(|component| !matches!(**component, DiffComponent::Unchanged(_, _)))
+            .count()
+    };
+
+    assert_eq!(4, changed(line_diff("a\r\nb\r\n", "a\nb\n")));
+    assert_eq!(0, changed(line_diff_with("a\r\nb\r\n", "a\nb\n", LineEndings::IgnoreCr)));
+    assert_eq!(2, changed(line_diff_with("a\r\nb\r\n", "a\nb", LineEndings::IgnoreCr)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_line_diff_reader() {
+    use std::io::Cursor;
+
+    let a = "a\nb\nb\nc\nd";
+    let b = "b\nc\nb\nd\n";
+
+    let diff = line_diff_reader(Cursor::new(a), Cursor::new(b)).unwrap();
+    assert_eq!(line_diff(a, b), diff);
+
+    let invalid = Cursor::new(vec![b'a', b'\n', 0xff, b'\n']);
+    let err = line_diff_reader(Cursor::new(a), invalid).unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+}
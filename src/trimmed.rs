@@ -0,0 +1,155 @@
+//! Skipping the common prefix and suffix of two sequences before building an `LcsTable`.
+
+use {DiffComponent, LcsTable};
+use util::{common_prefix_len, common_suffix_len};
+use prelude::*;
+
+/// An `LcsTable` that only covers the part of `a` and `b` between their common prefix and common
+/// suffix. For large, mostly-identical inputs such as two revisions of a source file, this is
+/// dramatically cheaper to construct than a full `LcsTable`.
+///
+/// The results are exactly the ones a full `LcsTable` over `a` and `b` would give.
+#[derive(Debug)]
+pub struct TrimmedLcsTable<'a, T: 'a> {
+    table: LcsTable<'a, T>,
+    prefix: usize,
+    suffix: usize,
+
+    a: &'a [T],
+    b: &'a [T]
+}
+
+impl<'a, T> TrimmedLcsTable<'a, T> where T: Eq {
+    /// Constructs a TrimmedLcsTable for matching between two sequences `a` and `b`.
+    pub fn new(a: &'a [T], b: &'a [T]) -> TrimmedLcsTable<'a, T> {
+        let prefix = common_prefix_len(a, b);
+        let suffix = common_suffix_len(a, b);
+
+        let table = LcsTable::new(&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+        TrimmedLcsTable { table, prefix, suffix, a, b }
+    }
+
+    /// Gets the length of the longest common subsequence between `a` and `b`. See
+    /// `LcsTable::length`.
+    pub fn length(&self) -> usize {
+        self.prefix + self.table.length() + self.suffix
+    }
+
+    /// Gets the longest common subsequence between `a` and `b`. See
+    /// `LcsTable::longest_common_subsequence`.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::TrimmedLcsTable;
+    ///
+    /// let a: Vec<_> = "a--b---c".chars().collect();
+    /// let b: Vec<_> = "a-bc".chars().collect();
+    ///
+    /// let table = TrimmedLcsTable::new(&a, &b);
+    /// let lcs = table.longest_common_subsequence();
+    ///
+    /// assert_eq!(vec![(&'a', &'a'), (&'-', &'-'), (&'b', &'b'), (&'c', &'c')], lcs);
+    /// ```
+    pub fn longest_common_subsequence(&self) -> Vec<(&T, &T)> {
+        self.diff().into_iter()
+            .filter_map(|component| match component {
+                DiffComponent::Unchanged(elem_a, elem_b) => Some((elem_a, elem_b)),
+                _ => None
+            })
+            .collect()
+    }
+
+    /// Computes a diff from `a` to `b`. See `LcsTable::diff`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, TrimmedLcsTable};
+    ///
+    /// let a: Vec<_> = "axb".chars().collect();
+    /// let b: Vec<_> = "abc".chars().collect();
+    ///
+    /// let table = TrimmedLcsTable::new(&a, &b);
+    /// assert_eq!(table.diff(), vec![
+    ///     DiffComponent::Unchanged(&'a', &'a'),
+    ///     DiffComponent::Deletion(&'x'),
+    ///     DiffComponent::Unchanged(&'b', &'b'),
+    ///     DiffComponent::Insertion(&'c')
+    /// ]);
+    /// ```
+    pub fn diff(&self) -> Vec<DiffComponent<&T>> {
+        let middle = self.table.diff();
+
+        // A full table would agree with the middle one until backtracking reaches the edge of the
+        // common prefix. From there, the middle table only has insertions (or deletions) left,
+        // whereas the full table can still match those elements against the prefix.
+        let (mut leftover_a, mut leftover_b) = (0, 0);
+        for component in &middle {
+            match *component {
+                DiffComponent::Insertion(_) if leftover_a == 0 => leftover_b += 1,
+                DiffComponent::Deletion(_) if leftover_b == 0 => leftover_a += 1,
+                _ => break
+            }
+        }
+
+        let mut diff = self.prefix_diff(self.prefix + leftover_a, self.prefix + leftover_b);
+        diff.extend(middle.into_iter().skip(leftover_a + leftover_b));
+        diff.extend(self.a[self.a.len() - self.suffix..].iter()
+            .zip(&self.b[self.b.len() - self.suffix..])
+            .map(|(elem_a, elem_b)| DiffComponent::Unchanged(elem_a, elem_b)));
+        diff
+    }
+
+    /// Backtracks the way `LcsTable::diff` would from `(i, j)`, where at least one of `i` and `j`
+    /// is within the common prefix. In that region, the LCS length is always `min(i, j)`.
+    fn prefix_diff(&self, mut i: usize, mut j: usize) -> Vec<DiffComponent<&T>> {
+        let mut diff = vec![];
+
+        while i > 0 || j > 0 {
+            // In the prefix region, `lengths[i][j - 1] > lengths[i - 1][j]` is just `i < j`.
+            if i == 0 || (j > 0 && self.a[i - 1] != self.b[j - 1] && i < j) {
+                diff.push(DiffComponent::Insertion(&self.b[j - 1]));
+                j -= 1;
+            } else if j == 0 || self.a[i - 1] != self.b[j - 1] {
+                diff.push(DiffComponent::Deletion(&self.a[i - 1]));
+                i -= 1;
+            } else {
+                diff.push(DiffComponent::Unchanged(&self.a[i - 1], &self.b[j - 1]));
+                i -= 1;
+                j -= 1;
+            }
+        }
+
+        diff.reverse();
+        diff
+    }
+}
+
+#[test]
+fn test_trimmed_matches_full_table() {
+    use random_strings;
+
+    let mut cases = random_strings(0xd1b5_4a32_d192_ed03, 500);
+    cases.push(("ay".chars().collect(), "aaz".chars().collect()));
+    cases.push(("abc".chars().collect(), "abc".chars().collect()));
+
+    for (a, b) in cases {
+        let full = LcsTable::new(&a, &b);
+        let trimmed = TrimmedLcsTable::new(&a, &b);
+
+        assert_eq!(full.length(), trimmed.length());
+        assert_eq!(full.diff(), trimmed.diff());
+        assert_eq!(full.longest_common_subsequence(), trimmed.longest_common_subsequence());
+    }
+}
+
+#[test]
+fn test_trimmed_skips_common_ends() {
+    let a: Vec<_> = "abcXdef".chars().collect();
+    let b: Vec<_> = "abcYdef".chars().collect();
+
+    let table = TrimmedLcsTable::new(&a, &b);
+    assert_eq!((table.prefix, table.suffix), (3, 3));
+    assert_eq!(table.length(), 6);
+}
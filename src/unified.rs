@@ -0,0 +1,193 @@
+//! Rendering diffs in the [unified format][unified] understood by `patch` and friends.
+//!
+//! [unified]: https://www.gnu.org/software/diffutils/manual/html_node/Unified-Format.html
+
+use std::fmt::{Display, Write};
+
+use {ContextBlock, Diff, DiffComponent, LcsTable};
+use prelude::*;
+
+impl<'a, T, E> LcsTable<'a, T, E> where T: Display, E: Fn(&T, &T) -> bool {
+    /// Renders the diff from `a` to `b` in the unified format, treating each element as a line.
+    ///
+    /// Changes are grouped into hunks, each surrounded by up to `context` unchanged lines. Changes
+    /// separated by no more than `2 * context` unchanged lines share a hunk. If `a` and `b` are
+    /// identical, the result is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec!["one", "two", "three"];
+    /// let b = vec!["one", "2", "three"];
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.unified_diff(1), "@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n");
+    /// ```
+    pub fn unified_diff(&self, context: usize) -> String {
+        self.diff().to_unified(context)
+    }
+
+    /// Renders the diff from `a` to `b` like `unified_diff`, with the headers `git diff` puts
+    /// before the hunks of a file, so the result can be fed to `git apply`. `path_a` and `path_b`
+    /// are the paths of the file before and after the change, without the `a/` and `b/` prefixes.
+    /// `git apply` rejects hunks without context unless given `--unidiff-zero`, so `context` should
+    /// usually be at least 1.
+    ///
+    /// If `a` and `b` are identical, the result is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a = vec!["one", "two", "three"];
+    /// let b = vec!["one", "2", "three"];
+    ///
+    /// let table = LcsTable::new(&a, &b);
+    /// assert_eq!(table.git_diff("numbers.txt", "numbers.txt", 1), "\
+    /// diff --git a/numbers.txt b/numbers.txt
+    /// --- a/numbers.txt
+    /// +++ b/numbers.txt
+    /// @@ -1,3 +1,3 @@
+    ///  one
+    /// -two
+    /// +2
+    ///  three
+    /// ");
+    /// ```
+    pub fn git_diff(&self, path_a: &str, path_b: &str, context: usize) -> String {
+        let hunks = self.unified_diff(context);
+        if hunks.is_empty() {
+            return hunks;
+        }
+
+        format!("diff --git a/{0} b/{1}\n--- a/{0}\n+++ b/{1}\n{2}", path_a, path_b, hunks)
+    }
+}
+
+impl<'a, T> Diff<'a, T> where T: Display {
+    /// Renders the diff in the unified format, treating each element as a line. See
+    /// `LcsTable::unified_diff`.
+    pub fn to_unified(&self, context: usize) -> String {
+        let mut out = String::new();
+        for block in self.contextual(context) {
+            write_hunk(&mut out, &block);
+        }
+
+        out
+    }
+}
+
+/// Writes a block of changes and their context as a hunk.
+fn write_hunk<T>(out: &mut String, block: &ContextBlock<&T>) where T: Display {
+    let components = || block.leading.iter().chain(&block.changes).chain(&block.trailing);
+
+    let (mut len_a, mut len_b) = (0, 0);
+    for component in components() {
+        match *component {
+            DiffComponent::Insertion(_) => len_b += 1,
+            DiffComponent::Unchanged(_, _) => { len_a += 1; len_b += 1; },
+            DiffComponent::Deletion(_) => len_a += 1
+        }
+    }
+
+    writeln!(out, "@@ -{} +{} @@", format_range(block.a_start, len_a),
+             format_range(block.b_start, len_b)).unwrap();
+
+    // Within a run of changes, deletions are conventionally listed before insertions.
+    let mut insertions = Vec::new();
+    for component in components() {
+        match *component {
+            DiffComponent::Insertion(elem) => insertions.push(elem),
+            DiffComponent::Unchanged(elem, _) => {
+                write_insertions(out, &mut insertions);
+                writeln!(out, " {}", elem).unwrap();
+            },
+            DiffComponent::Deletion(elem) => writeln!(out, "-{}", elem).unwrap()
+        }
+    }
+
+    write_insertions(out, &mut insertions);
+}
+
+fn write_insertions<T>(out: &mut String, insertions: &mut Vec<&T>) where T: Display {
+    for elem in insertions.drain(..) {
+        writeln!(out, "+{}", elem).unwrap();
+    }
+}
+
+/// Formats a hunk range the way `diff -u` does: an empty range refers to the line before it, and
+/// a range covering a single line omits its length.
+fn format_range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len)
+    }
+}
+
+#[test]
+fn test_unified_diff() {
+    let a: Vec<_> = (1..13).map(|n| format!("{}", n)).collect();
+    let mut b = a.clone();
+    b.remove(1);
+    b[9] = String::from("eleven");
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.unified_diff(3), "\
+@@ -1,5 +1,4 @@
+ 1
+-2
+ 3
+ 4
+ 5
+@@ -8,5 +7,5 @@
+ 8
+ 9
+ 10
+-11
++eleven
+ 12
+");
+}
+
+#[test]
+fn test_unified_diff_merges_nearby_changes() {
+    let a = vec!["a", "b", "c", "d", "e"];
+    let b = vec!["a", "B", "c", "d", "E"];
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.unified_diff(1), "@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n-e\n+E\n");
+    assert_eq!(table.unified_diff(0), "@@ -2 +2 @@\n-b\n+B\n@@ -5 +5 @@\n-e\n+E\n");
+}
+
+#[test]
+fn test_unified_diff_empty() {
+    let a = vec!["a", "b"];
+    let empty: Vec<&str> = vec![];
+
+    assert_eq!(LcsTable::new(&a, &a).unified_diff(3), "");
+    assert_eq!(LcsTable::new(&empty, &empty).unified_diff(3), "");
+    assert_eq!(LcsTable::new(&empty, &a).unified_diff(3), "@@ -0,0 +1,2 @@\n+a\n+b\n");
+}
+
+#[test]
+fn test_git_diff() {
+    let a = vec!["a", "b", "c"];
+    let b = vec!["a", "c", "d"];
+
+    let table = LcsTable::new(&a, &b);
+    assert_eq!(table.git_diff("old.txt", "new.txt", 0), "\
+diff --git a/old.txt b/new.txt
+--- a/old.txt
++++ b/new.txt
+@@ -2 +1,0 @@
+-b
+@@ -3,0 +3 @@
++d
+");
+
+    assert_eq!(LcsTable::new(&a, &a).git_diff("a.txt", "a.txt", 3), "");
+}
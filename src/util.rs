@@ -0,0 +1,127 @@
+//! Small helpers for working with pairs of sequences.
+
+use std::ptr;
+
+use {DiffComponent, LcsTable};
+use prelude::*;
+
+/// Gets the number of leading elements `a` and `b` have in common.
+///
+/// Example:
+///
+/// ```
+/// use lcs::util::common_prefix_len;
+///
+/// let a: Vec<_> = "abcx".chars().collect();
+/// let b: Vec<_> = "abcy".chars().collect();
+///
+/// assert_eq!(3, common_prefix_len(&a, &b));
+/// ```
+pub fn common_prefix_len<T>(a: &[T], b: &[T]) -> usize where T: Eq {
+    a.iter().zip(b).take_while(|&(x, y)| x == y).count()
+}
+
+/// Gets the number of trailing elements `a` and `b` have in common, not counting any that are
+/// already part of their common prefix. This way, `common_prefix_len(a, b) + common_suffix_len(a,
+/// b)` never exceeds the length of either sequence, even when `a` and `b` are equal or one is a
+/// prefix of the other.
+///
+/// Example:
+///
+/// ```
+/// use lcs::util::common_suffix_len;
+///
+/// let a: Vec<_> = "xabc".chars().collect();
+/// let b: Vec<_> = "yabc".chars().collect();
+/// assert_eq!(3, common_suffix_len(&a, &b));
+///
+/// // All of "aa" is the common prefix, so there's no suffix left to share.
+/// let a: Vec<_> = "aa".chars().collect();
+/// let b: Vec<_> = "aaa".chars().collect();
+/// assert_eq!(0, common_suffix_len(&a, &b));
+/// ```
+pub fn common_suffix_len<T>(a: &[T], b: &[T]) -> usize where T: Eq {
+    let prefix = common_prefix_len(a, b);
+    a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|&(x, y)| x == y).count()
+}
+
+/// Compares two common subsequences of the same `a` and `b`, such as ones found with different
+/// tie-breaking, showing where they chose different matches. Matches in `lcs1` but not `lcs2` are
+/// deletions, and matches in `lcs2` but not `lcs1` are insertions.
+///
+/// Matches are compared by position rather than by value, so `lcs1` and `lcs2` should refer to
+/// elements of the same sequences: matching the first `'a'` of `a` is different from matching the
+/// second one.
+///
+/// Example:
+///
+/// ```
+/// use lcs::{DiffComponent, LcsTable};
+/// use lcs::util::subsequence_diff;
+///
+/// let a: Vec<_> = "axb".chars().collect();
+/// let b: Vec<_> = "ayb".chars().collect();
+/// let table = LcsTable::new(&a, &b);
+///
+/// let first = table.longest_common_subsequence();
+/// let second = vec![(&a[0], &b[0])];
+/// assert_eq!(subsequence_diff(&first, &second), vec![
+///     DiffComponent::Unchanged((&'a', &'a'), (&'a', &'a')),
+///     DiffComponent::Deletion((&'b', &'b'))
+/// ]);
+/// ```
+pub fn subsequence_diff<'a, T>(lcs1: &[(&'a T, &'a T)], lcs2: &[(&'a T, &'a T)])
+        -> Vec<DiffComponent<(&'a T, &'a T)>> {
+    let same_match = |x: &(&T, &T), y: &(&T, &T)| ptr::eq(x.0, y.0) && ptr::eq(x.1, y.1);
+
+    LcsTable::new_by(lcs1, lcs2, same_match).diff_iter()
+        .map(|component| match component {
+            DiffComponent::Insertion(&pair) => DiffComponent::Insertion(pair),
+            DiffComponent::Unchanged(&pair_1, &pair_2) => DiffComponent::Unchanged(pair_1, pair_2),
+            DiffComponent::Deletion(&pair) => DiffComponent::Deletion(pair)
+        })
+        .collect()
+}
+
+#[test]
+fn test_common_prefix_and_suffix() {
+    let cases: &[(&str, &str, usize, usize)] = &[
+        ("", "", 0, 0),
+        ("abc", "", 0, 0),
+        ("abc", "abc", 3, 0),
+        ("abcxdef", "abcydef", 3, 3),
+        ("aXa", "aXYa", 2, 1),
+        ("xyz", "abc", 0, 0)
+    ];
+
+    for &(a, b, prefix, suffix) in cases {
+        let a: Vec<_> = a.chars().collect();
+        let b: Vec<_> = b.chars().collect();
+
+        assert_eq!(prefix, common_prefix_len(&a, &b));
+        assert_eq!(suffix, common_suffix_len(&a, &b));
+    }
+}
+
+#[test]
+fn test_subsequence_diff() {
+    let a: Vec<_> = "abca".chars().collect();
+    let b: Vec<_> = "ab".chars().collect();
+
+    // Equal by value, but the second ends by matching a different 'a' than the first.
+    let first = vec![(&a[0], &b[0]), (&a[1], &b[1])];
+    let second = vec![(&a[3], &b[0])];
+    let third = vec![(&a[0], &b[0])];
+
+    assert_eq!(subsequence_diff(&first, &second), vec![
+        DiffComponent::Insertion((&'a', &'a')),
+        DiffComponent::Deletion((&'a', &'a')),
+        DiffComponent::Deletion((&'b', &'b'))
+    ]);
+    assert_eq!(subsequence_diff(&first, &third), vec![
+        DiffComponent::Unchanged((&'a', &'a'), (&'a', &'a')),
+        DiffComponent::Deletion((&'b', &'b'))
+    ]);
+    assert!(subsequence_diff(&first, &first).iter()
+        .all(|component| matches!(*component, DiffComponent::Unchanged(_, _))));
+}
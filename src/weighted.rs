@@ -0,0 +1,161 @@
+//! Longest common subsequences where some matches are worth more than others.
+
+use std::cmp;
+
+use LcsTable;
+use prelude::*;
+
+/// Like an `LcsTable`, but for finding the common subsequence with the greatest total weight,
+/// rather than the most elements. See `LcsTable::weighted`.
+#[derive(Debug, Clone)]
+pub struct WeightedLcsTable<'a, T: 'a> {
+    scores: Vec<i64>,
+
+    a: &'a [T],
+    b: &'a [T]
+}
+
+impl<'a, T> LcsTable<'a, T> {
+    /// Constructs a table for finding the common subsequence of `a` and `b` whose elements have
+    /// the greatest total `weight`. Matching a rare or important element can then be worth more
+    /// than matching several common ones. With a weight of 1 for every element, this is the same
+    /// as an ordinary `LcsTable`.
+    ///
+    /// Elements with a negative weight are never worth matching, and so are never part of the
+    /// result.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use lcs::LcsTable;
+    ///
+    /// let a: Vec<_> = "abc".chars().collect();
+    /// let b: Vec<_> = "cab".chars().collect();
+    ///
+    /// let table = LcsTable::weighted(&a, &b, |&c| if c == 'c' { 5 } else { 1 });
+    /// assert_eq!(5, table.weighted_score());
+    /// assert_eq!(vec![(&'c', &'c')], table.weighted_subsequence());
+    /// ```
+    pub fn weighted<F>(a: &'a [T], b: &'a [T], weight: F) -> WeightedLcsTable<'a, T>
+            where T: Eq, F: Fn(&T) -> i64 {
+        let width = b.len() + 1;
+        let mut scores = vec![0; (a.len() + 1) * width];
+
+        for i in 0..a.len() {
+            let weight = weight(&a[i]);
+
+            for j in 0..b.len() {
+                let skip = cmp::max(scores[(i + 1) * width + j], scores[i * width + j + 1]);
+                scores[(i + 1) * width + j + 1] = if a[i] == b[j] {
+                    cmp::max(skip, scores[i * width + j] + weight)
+                } else {
+                    skip
+                };
+            }
+        }
+
+        WeightedLcsTable { scores, a, b }
+    }
+}
+
+impl<'a, T> WeightedLcsTable<'a, T> where T: Eq {
+    /// Gets the total weight of the heaviest common subsequence between `a` and `b`.
+    pub fn weighted_score(&self) -> i64 {
+        self.score(self.a.len(), self.b.len())
+    }
+
+    /// Gets the heaviest common subsequence between `a` and `b`, in the same form as
+    /// `LcsTable::longest_common_subsequence`.
+    pub fn weighted_subsequence(&self) -> Vec<(&T, &T)> {
+        let mut subsequence = Vec::new();
+        let (mut i, mut j) = (self.a.len(), self.b.len());
+
+        while i > 0 && j > 0 {
+            let score = self.score(i, j);
+
+            if score == self.score(i - 1, j) {
+                i -= 1;
+            } else if score == self.score(i, j - 1) {
+                j -= 1;
+            } else {
+                // Neither skip explains the score, so it has to come from matching these two.
+                subsequence.push((&self.a[i - 1], &self.b[j - 1]));
+                i -= 1;
+                j -= 1;
+            }
+        }
+
+        subsequence.reverse();
+        subsequence
+    }
+
+    fn score(&self, i: usize, j: usize) -> i64 {
+        self.scores[i * (self.b.len() + 1) + j]
+    }
+}
+
+#[test]
+fn test_weighted_unit_weights() {
+    use random_strings;
+
+    for (a, b) in random_strings(0x5851_f42d_4c95_7f2d, 300) {
+        let table = LcsTable::weighted(&a, &b, |_| 1);
+        let subsequence = table.weighted_subsequence();
+
+        assert_eq!(LcsTable::new(&a, &b).length() as i64, table.weighted_score());
+        assert_eq!(table.weighted_score(), subsequence.len() as i64);
+    }
+}
+
+#[test]
+fn test_weighted_scores() {
+    use random_strings;
+
+    let weight = |c: &char| match *c {
+        'a' => 3,
+        'b' => -1,
+        'c' => 0,
+        _ => 1
+    };
+
+    // The greatest weight of any common subsequence, found by trying every subsequence of `a`.
+    let brute_force = |a: &[char], b: &[char]| -> i64 {
+        (0u32..1 << a.len())
+            .filter_map(|mask| {
+                let mut b = b.iter();
+                let mut total = 0;
+                for (k, elem) in a.iter().enumerate() {
+                    if mask & (1 << k) != 0 {
+                        if !b.any(|other| other == elem) {
+                            return None;
+                        }
+                        total += weight(elem);
+                    }
+                }
+                Some(total)
+            })
+            .max()
+            .unwrap()
+    };
+
+    for (a, b) in random_strings(0x1405_7b7e_f767_814f, 300) {
+        let table = LcsTable::weighted(&a, &b, weight);
+        let subsequence = table.weighted_subsequence();
+
+        let total: i64 = subsequence.iter().map(|&(elem_a, _)| weight(elem_a)).sum();
+        assert_eq!(table.weighted_score(), total);
+        assert!(subsequence.iter().all(|&(elem_a, elem_b)| elem_a == elem_b && *elem_a != 'b'));
+
+        // The unweighted LCS, minus its negative elements, is a common subsequence with no
+        // match worth less than 0.
+        let without_b: i64 = LcsTable::new(&a, &b).longest_common_subsequence().into_iter()
+            .filter(|&(elem_a, _)| *elem_a != 'b')
+            .map(|(elem_a, _)| weight(elem_a))
+            .sum();
+        assert!(table.weighted_score() >= without_b);
+
+        if a.len() <= 12 {
+            assert_eq!(brute_force(&a, &b), table.weighted_score());
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Diffing many pairs of sequences without allocating a new table for each pair.
+
+use {fill_into, DiffComponent};
+use prelude::*;
+
+/// Scratch space for computing diffs one pair of sequences after another. Building an `LcsTable`
+/// allocates a new table each time, which adds up when diffing thousands of small pairs in a loop.
+/// A workspace instead keeps its table around between calls to `diff_into`, and only grows it when
+/// a pair needs more room than any before it.
+///
+/// A workspace doesn't hold on to any sequences, so the same one can be reused for pairs of any
+/// element type.
+#[derive(Debug, Clone, Default)]
+pub struct LcsWorkspace {
+    lengths: Vec<usize>
+}
+
+impl LcsWorkspace {
+    /// Constructs an empty workspace. Its table is allocated by the first call to `diff_into`.
+    pub fn new() -> LcsWorkspace {
+        LcsWorkspace::default()
+    }
+
+    /// Computes a diff from `a` to `b`, replacing the contents of `out` with it. The diff is the
+    /// same one `LcsTable::diff` would give.
+    ///
+    /// Reusing `out` across calls, as well as the workspace, avoids allocating anything once both
+    /// are big enough.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use lcs::{DiffComponent, LcsTable, LcsWorkspace};
+    ///
+    /// let pairs = [("axb", "abc"), ("kitten", "sitting")];
+    /// let pairs: Vec<(Vec<_>, Vec<_>)> = pairs.iter()
+    ///     .map(|&(a, b)| (a.chars().collect(), b.chars().collect()))
+    ///     .collect();
+    ///
+    /// let mut workspace = LcsWorkspace::new();
+    /// let mut diff = Vec::new();
+    ///
+    /// for (a, b) in &pairs {
+    ///     workspace.diff_into(a, b, &mut diff);
+    ///     assert_eq!(LcsTable::new(a, b).diff(), diff);
+    /// }
+    /// ```
+    pub fn diff_into<'a, T>(&mut self, a: &'a [T], b: &'a [T],
+                            out: &mut Vec<DiffComponent<&'a T>>) where T: Eq {
+        let (n, m) = (a.len(), b.len());
+        let width = m + 1;
+
+        fill_into(&mut self.lengths, n, m, |i, j| a[i] == b[j]);
+        let lengths = &self.lengths;
+
+        out.clear();
+        let (mut i, mut j) = (n, m);
+
+        while i > 0 || j > 0 {
+            if i == 0 {
+                out.push(DiffComponent::Insertion(&b[j - 1]));
+                j -= 1;
+            } else if j == 0 {
+                out.push(DiffComponent::Deletion(&a[i - 1]));
+                i -= 1;
+            } else if a[i - 1] == b[j - 1] {
+                out.push(DiffComponent::Unchanged(&a[i - 1], &b[j - 1]));
+                i -= 1;
+                j -= 1;
+            } else if lengths[i * width + j - 1] > lengths[(i - 1) * width + j] {
+                out.push(DiffComponent::Insertion(&b[j - 1]));
+                j -= 1;
+            } else {
+                out.push(DiffComponent::Deletion(&a[i - 1]));
+                i -= 1;
+            }
+        }
+
+        out.reverse();
+    }
+}
+
+#[test]
+fn test_workspace_matches_table() {
+    use {random_strings, LcsTable};
+
+    let mut workspace = LcsWorkspace::new();
+    let mut diff = Vec::new();
+
+    let mut cases = random_strings(0xc2b2_ae3d_27d4_eb4f, 500);
+    cases.push((vec![], vec![]));
+
+    for (a, b) in &cases {
+        workspace.diff_into(a, b, &mut diff);
+        assert_eq!(LcsTable::new(a, b).diff(), diff);
+    }
+}
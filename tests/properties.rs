@@ -0,0 +1,99 @@
+//! Property tests checking diffs against their inputs, and table lengths against a brute-force
+//! search, over randomly generated sequences.
+
+extern crate lcs;
+extern crate proptest;
+
+use lcs::{apply, hirschberg, invert, myers_diff, DiffComponent, LcsTable};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+/// A sequence over a small alphabet, so that the same element comes up many times.
+fn sequence(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    vec(0u8..4, 0..max_len)
+}
+
+/// A pair of sequences, either unrelated or with the second made by editing the first. Unrelated
+/// pairs have little in common, so most of the interesting cases come from edited ones.
+fn pair(max_len: usize) -> impl Strategy<Value = (Vec<u8>, Vec<u8>)> {
+    let unrelated = (sequence(max_len), sequence(max_len));
+    let edited = (sequence(max_len), vec((any::<bool>(), any::<prop::sample::Index>(), 0u8..4),
+                                         0..8))
+        .prop_map(|(a, edits)| {
+            let mut b = a.clone();
+            for (insert, index, elem) in edits {
+                if insert {
+                    let at = index.index(b.len() + 1);
+                    b.insert(at, elem);
+                } else if !b.is_empty() {
+                    let at = index.index(b.len());
+                    b.remove(at);
+                }
+            }
+
+            (a, b)
+        });
+
+    prop_oneof![unrelated, edited]
+}
+
+/// Whether `sub` is a subsequence of `seq`.
+fn is_subsequence(sub: &[u8], seq: &[u8]) -> bool {
+    let mut seq = seq.iter();
+    sub.iter().all(|elem| seq.any(|other| other == elem))
+}
+
+/// The length of the longest subsequence of `a` that's also a subsequence of `b`, by trying every
+/// subsequence of `a`.
+fn brute_force_lcs_length(a: &[u8], b: &[u8]) -> usize {
+    (0u32..1 << a.len())
+        .map(|mask| -> Vec<u8> {
+            a.iter().enumerate().filter(|&(k, _)| mask & (1 << k) != 0).map(|(_, &x)| x).collect()
+        })
+        .filter(|sub| is_subsequence(sub, b))
+        .map(|sub| sub.len())
+        .max()
+        .unwrap()
+}
+
+fn changes<T>(diff: &[DiffComponent<T>]) -> usize {
+    diff.iter().filter(|component| !matches!(**component, DiffComponent::Unchanged(_, _))).count()
+}
+
+proptest! {
+    #[test]
+    fn diff_round_trips((a, b) in pair(40)) {
+        let table = LcsTable::new(&a, &b);
+        let diff = table.diff();
+
+        prop_assert_eq!(apply(&a, &diff), Ok(b.clone()));
+        prop_assert_eq!(apply(&b, &invert(table.diff())), Ok(a.clone()));
+    }
+
+    #[test]
+    fn diff_keeps_a_longest_common_subsequence((a, b) in pair(40)) {
+        let table = LcsTable::new(&a, &b);
+        let diff = table.diff();
+
+        prop_assert_eq!(diff.len() - changes(&diff), table.length());
+        prop_assert_eq!(changes(&diff), table.edit_distance());
+        prop_assert_eq!(changes(&myers_diff(&a, &b)), table.edit_distance());
+    }
+
+    #[test]
+    fn length_matches_brute_force((a, b) in pair(12)) {
+        let table = LcsTable::new(&a, &b);
+
+        prop_assert_eq!(table.length(), brute_force_lcs_length(&a, &b));
+        prop_assert_eq!(table.length(), LcsTable::new(&b, &a).length());
+    }
+
+    #[test]
+    fn hirschberg_matches_table_length((a, b) in pair(40)) {
+        let lcs = hirschberg::longest_common_subsequence(&a, &b);
+        let elems: Vec<u8> = lcs.iter().map(|&(&elem_a, _)| elem_a).collect();
+
+        prop_assert_eq!(lcs.len(), LcsTable::new(&a, &b).length());
+        prop_assert!(is_subsequence(&elems, &a) && is_subsequence(&elems, &b));
+    }
+}